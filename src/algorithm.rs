@@ -0,0 +1,130 @@
+//! What every optimizer in the crate offers, so that callers can choose one at
+//! runtime and drive it a generation at a time.
+
+use std::rc::Rc;
+
+use rand::rngs::StdRng;
+
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::engine::{Encoding, Engine};
+use crate::evaluation::{Evaluator, RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::{pareto_front, Individual};
+use crate::solution::{Problem, Solution, Standalone};
+
+pub trait Algorithm<S: Solution> {
+    /// Forget any previous run and evaluate the initial population, which is
+    /// recorded as generation 0.
+    fn initialize(&mut self) -> Result<(), RunError>;
+
+    /// Advance an initialized run by one generation and record it.
+    fn step(&mut self) -> Result<(), RunError>;
+
+    /// Whether the configured termination criteria are met
+    fn finished(&self) -> bool;
+
+    /// Initialize, then step until finished.
+    fn run(&mut self) -> Result<RunStatistics, RunError> {
+        self.initialize()?;
+
+        while !self.finished() {
+            self.step()?;
+        }
+
+        Ok(self.statistics().clone())
+    }
+
+    /// Show every recorded generation to `observer`.
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>);
+
+    /// The individuals the algorithm currently works with
+    fn population(&self) -> &[Individual<S>];
+
+    /// Best solutions of the whole run, if the config asks for an archive
+    fn archive(&self) -> Option<&Archive<S>>;
+
+    /// Best feasible solution ever evaluated, if the problem has a single objective
+    fn best(&self) -> Option<&Individual<S>>;
+
+    /// The best solutions found so far: the archive if there is one, otherwise
+    /// the feasible members of the population not dominated by any other
+    fn result(&self) -> Vec<&Individual<S>> {
+        match self.archive() {
+            Some(archive) => archive.members().iter().collect(),
+            None => pareto_front(self.population()),
+        }
+    }
+
+    fn config(&self) -> &Config;
+
+    fn statistics(&self) -> &RunStatistics;
+
+    /// Statistics of every generation of the last run, starting with the initial population
+    fn history(&self) -> &RunHistory;
+}
+
+mod sealed {
+    use crate::engine::Engine;
+    use crate::solution::Solution;
+
+    /// The engine an algorithm runs on, for the methods of [`super::Build`]
+    pub trait WithEngine<S: Solution> {
+        fn from_engine(engine: Engine<S>) -> Self;
+
+        fn engine_mut(&mut self) -> &mut Engine<S>;
+    }
+}
+
+pub(crate) use sealed::WithEngine;
+
+/// Constructors and settings every algorithm shares. Parameters of a single
+/// algorithm are set by its own methods, such as
+/// [`Moead::with_params`](crate::moead::Moead::with_params).
+pub trait Build<S: Solution>: WithEngine<S> + Sized {
+    /// Draws the initial population from [`Standalone::random`].
+    fn new(config: Config) -> Self
+    where
+        S: Standalone,
+    {
+        Self::with_sampler(config, |rng| S::random(rng))
+    }
+
+    /// Draws the initial population from an instance of a problem whose size
+    /// and data are chosen at runtime.
+    fn with_problem(config: Config, problem: impl Problem<Solution = S> + 'static) -> Self {
+        let problem = Rc::new(problem);
+        let encoding = Encoding::of(&problem);
+        let mut algorithm = Self::with_sampler(config, move |rng| problem.random(rng));
+        algorithm.engine_mut().set_encoding(encoding);
+        algorithm
+    }
+
+    /// Draws the initial population from `sampler`.
+    fn with_sampler(config: Config, sampler: impl FnMut(&mut StdRng) -> S + 'static) -> Self {
+        Self::from_engine(Engine::new(config, Box::new(sampler)))
+    }
+
+    /// Start the initial population with `solutions`, such as the results of
+    /// an earlier run, and draw only the rest; at most the population size of
+    /// them are used.
+    fn with_initial_solutions(mut self, solutions: impl IntoIterator<Item = S>) -> Self {
+        self.engine_mut().set_initial_solutions(solutions.into_iter().collect());
+        self
+    }
+
+    /// Evaluate candidates with `evaluator` instead of calling their objective
+    /// functions on the current thread.
+    fn with_evaluator(mut self, evaluator: impl Evaluator<S> + 'static) -> Self {
+        self.engine_mut().set_evaluator(Box::new(evaluator));
+        self
+    }
+
+    /// Show every generation to `observer` as the run progresses.
+    fn with_observer(mut self, observer: impl Observer<S> + 'static) -> Self {
+        self.engine_mut().add_observer(Box::new(observer));
+        self
+    }
+}
+
+impl<S: Solution, A: WithEngine<S>> Build<S> for A {}
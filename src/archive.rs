@@ -0,0 +1,152 @@
+//! An external archive of the best solutions evaluated during a run, so that
+//! none are lost when the population moves on.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dominance::dominates;
+use crate::individual::Individual;
+use crate::nsga2::crowding_distance;
+
+/// Which solutions an [`Archive`] keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArchivePolicy {
+    /// Every non-dominated solution, however many there are
+    Unbounded,
+    /// At most one solution per box of side `epsilon` in objective space, and
+    /// none whose box is dominated (Laumanns et al., 2002)
+    EpsilonDominance { epsilon: f64 },
+    /// Non-dominated solutions, pruned by crowding distance beyond `capacity`
+    Crowding { capacity: usize },
+}
+
+/// Feasible solutions that no other evaluated feasible solution dominates,
+/// subject to the [`ArchivePolicy`].
+#[derive(Clone, Debug)]
+pub struct Archive<S> {
+    policy: ArchivePolicy,
+    members: Vec<Individual<S>>,
+}
+
+impl<S: Clone> Archive<S> {
+    pub fn new(policy: ArchivePolicy) -> Self {
+        Self {
+            policy,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn policy(&self) -> ArchivePolicy {
+        self.policy
+    }
+
+    pub fn members(&self) -> &[Individual<S>] {
+        &self.members
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.members.clear();
+    }
+
+    /// Add `candidate` if it earns a place, evicting the members it makes
+    /// redundant. Returns whether it was added.
+    pub fn offer(&mut self, candidate: &Individual<S>) -> bool {
+        if !candidate.feasible || candidate.objectives.iter().any(|value| !value.is_finite()) {
+            return false;
+        }
+
+        match self.policy {
+            ArchivePolicy::Unbounded => self.offer_non_dominated(candidate),
+            ArchivePolicy::Crowding { capacity } => {
+                let added = self.offer_non_dominated(candidate);
+
+                if self.members.len() > capacity {
+                    self.prune();
+                }
+
+                // The candidate itself may have been the most crowded
+                added && self.members.iter().any(|member| member.objectives == candidate.objectives)
+            }
+            ArchivePolicy::EpsilonDominance { epsilon } => self.offer_boxed(candidate, epsilon),
+        }
+    }
+
+    fn offer_non_dominated(&mut self, candidate: &Individual<S>) -> bool {
+        let redundant = self
+            .members
+            .iter()
+            .any(|member| member.objectives == candidate.objectives || member.dominates(candidate));
+
+        if redundant {
+            return false;
+        }
+
+        self.members.retain(|member| !candidate.dominates(member));
+        self.members.push(candidate.clone());
+        true
+    }
+
+    /// Drop the member with the smallest crowding distance.
+    fn prune(&mut self) {
+        let everyone = (0..self.members.len()).collect::<Vec<_>>();
+        let distances = crowding_distance(&self.members, &everyone);
+
+        if let Some(crowded) = (0..self.members.len()).min_by(|&a, &b| distances[a].total_cmp(&distances[b])) {
+            self.members.remove(crowded);
+        }
+    }
+
+    fn offer_boxed(&mut self, candidate: &Individual<S>, epsilon: f64) -> bool {
+        let corner = grid_box(&candidate.objectives, epsilon);
+
+        let mut same_box = None;
+        for (i, member) in self.members.iter().enumerate() {
+            let member_corner = grid_box(&member.objectives, epsilon);
+
+            if member_corner == corner {
+                same_box = Some(i);
+            } else if dominates(&member_corner, &corner) {
+                return false;
+            }
+        }
+
+        // Within a box, a dominating solution or else the one nearer the corner wins
+        if let Some(i) = same_box {
+            let member = &self.members[i];
+            let closer = corner_distance(&candidate.objectives, &corner, epsilon)
+                < corner_distance(&member.objectives, &corner, epsilon);
+
+            if !candidate.dominates(member) && (member.dominates(candidate) || !closer) {
+                return false;
+            }
+
+            self.members.swap_remove(i);
+        }
+
+        self.members
+            .retain(|member| !dominates(&corner, &grid_box(&member.objectives, epsilon)));
+        self.members.push(candidate.clone());
+        true
+    }
+}
+
+/// Lower corner of the box of side `epsilon` holding `objectives`, in box units
+fn grid_box(objectives: &[f64], epsilon: f64) -> Vec<f64> {
+    objectives.iter().map(|value| (value / epsilon).floor()).collect()
+}
+
+fn corner_distance(objectives: &[f64], corner: &[f64], epsilon: f64) -> f64 {
+    objectives
+        .iter()
+        .zip(corner)
+        .map(|(value, low)| (value - low * epsilon).powi(2))
+        .sum::<f64>()
+}
@@ -0,0 +1,511 @@
+//! Repeated runs of several configurations on several problems, summarized and
+//! compared with rank-sum tests.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::experiment::{
+    AlgorithmConfig, Experiment, ExperimentError, FileFormat, OperatorConfig, OutputConfig, ProblemConfig,
+    TerminationConfig,
+};
+use crate::metrics::{bounds, hypervolume, igd, nondominated, normalize};
+use crate::stats::{iqr, mean, median, rank_sum_test};
+
+/// Hypervolume is measured against this point after normalizing every
+/// objective of the reference front to `[0, 1]`.
+pub const HYPERVOLUME_REFERENCE: f64 = 1.1;
+
+/// Every configuration is run on every problem, once per seed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    #[serde(default = "default_seeds")]
+    pub seeds: usize,
+    #[serde(default)]
+    pub first_seed: u64,
+    pub problems: Vec<ProblemConfig>,
+    pub configurations: Vec<Configuration>,
+    /// Directory receiving the report. Nothing is written when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<PathBuf>,
+}
+
+fn default_seeds() -> usize {
+    10
+}
+
+/// Algorithm settings under comparison, named for the report.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Configuration {
+    pub label: String,
+    #[serde(default)]
+    pub algorithm: AlgorithmConfig,
+    #[serde(default)]
+    pub operators: OperatorConfig,
+    #[serde(default)]
+    pub termination: TerminationConfig,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Hypervolume,
+    Igd,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 2] = [Metric::Hypervolume, Metric::Igd];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Hypervolume => "hypervolume",
+            Metric::Igd => "igd",
+        }
+    }
+
+    pub fn higher_is_better(self) -> bool {
+        matches!(self, Metric::Hypervolume)
+    }
+}
+
+/// Measurements from a single run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    /// Label of the problem, unique within the suite (see [`Suite::problem_labels`])
+    pub problem: String,
+    pub configuration: String,
+    pub seed: u64,
+    pub hypervolume: f64,
+    pub igd: f64,
+    pub front_size: usize,
+    pub evaluations: usize,
+    pub seconds: f64,
+}
+
+impl RunRecord {
+    pub fn metric(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::Hypervolume => self.hypervolume,
+            Metric::Igd => self.igd,
+        }
+    }
+}
+
+/// Distribution of one metric over the seeds of one configuration on one problem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub problem: String,
+    pub configuration: String,
+    pub metric: Metric,
+    pub mean: f64,
+    pub median: f64,
+    pub iqr: f64,
+}
+
+/// Rank-sum test of one metric between two configurations on one problem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub problem: String,
+    pub metric: Metric,
+    pub first: String,
+    pub second: String,
+    pub p_value: f64,
+    /// Configuration with the better median, if the medians differ
+    pub better: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub runs: Vec<RunRecord>,
+    pub summaries: Vec<Summary>,
+    pub comparisons: Vec<Comparison>,
+}
+
+impl Suite {
+    /// Read a suite from a `.toml` or `.json` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ExperimentError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ExperimentError::Io(path.to_path_buf(), e))?;
+
+        FileFormat::of(path)?.parse(&text)
+    }
+
+    /// Check that every configuration has a label of its own, so that runs,
+    /// summaries and comparisons tell them apart.
+    pub fn validate(&self) -> Result<(), ExperimentError> {
+        for (i, configuration) in self.configurations.iter().enumerate() {
+            if configuration.label.is_empty() {
+                return Err(ExperimentError::Parse(format!("configuration {} has an empty label", i + 1)));
+            }
+            if self.configurations[..i].iter().any(|other| other.label == configuration.label) {
+                return Err(ExperimentError::Parse(format!(
+                    "configuration label {:?} is used more than once",
+                    configuration.label
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Report labels of the problems: their names, followed by `#` and their
+    /// position in the suite, counting from 1, where a name occurs more than once.
+    pub fn problem_labels(&self) -> Vec<String> {
+        self.problems
+            .iter()
+            .enumerate()
+            .map(|(i, problem)| {
+                let name = problem.name();
+                if self.problems.iter().filter(|other| other.name() == name).count() > 1 {
+                    format!("{}#{}", name, i + 1)
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// The experiment behind one cell of the suite.
+    pub fn experiment(&self, problem: &ProblemConfig, configuration: &Configuration, seed: u64) -> Experiment {
+        let mut algorithm = configuration.algorithm.clone();
+        algorithm.seed = Some(seed);
+
+        Experiment {
+            problem: problem.clone(),
+            algorithm,
+            operators: configuration.operators.clone(),
+            termination: configuration.termination.clone(),
+            output: OutputConfig::default(),
+            workers: None,
+        }
+    }
+
+    /// Run every cell, calling `progress` after each run.
+    ///
+    /// Hypervolume and IGD are measured against the known front of a problem
+    /// as soon as a run ends. Problems without one are measured against the
+    /// best front of all their runs, so the records passed to `progress` carry
+    /// NaN for them and only the report has the values.
+    pub fn run(&self, mut progress: impl FnMut(&RunRecord)) -> Result<Report, ExperimentError> {
+        self.validate()?;
+
+        let mut report = Report::default();
+
+        for (problem, label) in self.problems.iter().zip(self.problem_labels()) {
+            let known_front = problem.reference_front();
+            let mut fronts = Vec::new();
+
+            for configuration in &self.configurations {
+                for seed in (self.first_seed..).take(self.seeds) {
+                    let outcome = self.experiment(problem, configuration, seed).run()?;
+                    let front = outcome
+                        .pareto_front()
+                        .iter()
+                        .map(|individual| individual.objectives.clone())
+                        .collect::<Vec<_>>();
+
+                    let mut record = RunRecord {
+                        problem: label.clone(),
+                        configuration: configuration.label.clone(),
+                        seed,
+                        hypervolume: f64::NAN,
+                        igd: f64::NAN,
+                        front_size: front.len(),
+                        evaluations: outcome.statistics.evaluations,
+                        seconds: outcome.elapsed.as_secs_f64(),
+                    };
+
+                    if let Some(reference_front) = &known_front {
+                        measure(&mut record, &front, reference_front);
+                    }
+
+                    progress(&record);
+                    fronts.push((record, front));
+                }
+            }
+
+            // Without a known front, the best found by any run stands in for it
+            if known_front.is_none() {
+                let all = fronts.iter().flat_map(|(_, front)| front.iter().cloned()).collect::<Vec<_>>();
+                let reference_front = nondominated(&all);
+
+                for (record, front) in &mut fronts {
+                    measure(record, front, &reference_front);
+                }
+            }
+
+            let runs = fronts.into_iter().map(|(record, _)| record).collect::<Vec<_>>();
+            self.summarize(&label, &runs, &mut report);
+            report.runs.extend(runs);
+        }
+
+        Ok(report)
+    }
+
+    /// Summaries and comparisons of the `runs` of one problem
+    fn summarize(&self, problem: &str, runs: &[RunRecord], report: &mut Report) {
+        let values = |configuration: &str, metric: Metric| {
+            runs.iter()
+                .filter(|run| run.configuration == configuration)
+                .map(|run| run.metric(metric))
+                .filter(|value| !value.is_nan())
+                .collect::<Vec<_>>()
+        };
+
+        for metric in Metric::ALL {
+            for configuration in &self.configurations {
+                let sample = values(&configuration.label, metric);
+
+                report.summaries.push(Summary {
+                    problem: problem.to_string(),
+                    configuration: configuration.label.clone(),
+                    metric,
+                    mean: mean(&sample),
+                    median: median(&sample),
+                    iqr: iqr(&sample),
+                });
+            }
+
+            for (i, first) in self.configurations.iter().enumerate() {
+                for second in &self.configurations[i + 1..] {
+                    let a = values(&first.label, metric);
+                    let b = values(&second.label, metric);
+
+                    let (median_a, median_b) = (median(&a), median(&b));
+                    let better = if median_a == median_b || median_a.is_nan() || median_b.is_nan() {
+                        None
+                    } else if (median_a > median_b) == metric.higher_is_better() {
+                        Some(first.label.clone())
+                    } else {
+                        Some(second.label.clone())
+                    };
+
+                    report.comparisons.push(Comparison {
+                        problem: problem.to_string(),
+                        metric,
+                        first: first.label.clone(),
+                        second: second.label.clone(),
+                        p_value: rank_sum_test(&a, &b),
+                        better,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Hypervolume and IGD of `front`, both normalized to the bounds of `reference_front`
+fn measure(record: &mut RunRecord, front: &[Vec<f64>], reference_front: &[Vec<f64>]) {
+    if let Some((ideal, nadir)) = bounds(reference_front) {
+        let reference_point = vec![HYPERVOLUME_REFERENCE; ideal.len()];
+        let front = normalize(front, &ideal, &nadir);
+
+        record.hypervolume = hypervolume(&front, &reference_point);
+        record.igd = igd(&front, &normalize(reference_front, &ideal, &nadir));
+    }
+}
+
+/// `field` as a quoted CSV field, with the quotes inside it doubled
+fn quoted(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+impl Report {
+    /// Write `runs.csv`, `summary.csv` and `comparisons.csv` into `directory`.
+    pub fn write(&self, directory: impl AsRef<Path>) -> io::Result<()> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        let mut runs = BufWriter::new(File::create(directory.join("runs.csv"))?);
+        writeln!(runs, "problem,configuration,seed,hypervolume,igd,front_size,evaluations,seconds")?;
+        for run in &self.runs {
+            writeln!(
+                runs,
+                "{},{},{},{},{},{},{},{}",
+                quoted(&run.problem),
+                quoted(&run.configuration),
+                run.seed,
+                run.hypervolume,
+                run.igd,
+                run.front_size,
+                run.evaluations,
+                run.seconds
+            )?;
+        }
+        runs.flush()?;
+
+        let mut summary = BufWriter::new(File::create(directory.join("summary.csv"))?);
+        writeln!(summary, "problem,configuration,metric,mean,median,iqr")?;
+        for row in &self.summaries {
+            writeln!(
+                summary,
+                "{},{},{},{},{},{}",
+                quoted(&row.problem),
+                quoted(&row.configuration),
+                row.metric.name(),
+                row.mean,
+                row.median,
+                row.iqr
+            )?;
+        }
+        summary.flush()?;
+
+        let mut comparisons = BufWriter::new(File::create(directory.join("comparisons.csv"))?);
+        writeln!(comparisons, "problem,metric,first,second,p_value,better")?;
+        for row in &self.comparisons {
+            writeln!(
+                comparisons,
+                "{},{},{},{},{},{}",
+                quoted(&row.problem),
+                row.metric.name(),
+                quoted(&row.first),
+                quoted(&row.second),
+                row.p_value,
+                row.better.as_deref().map(quoted).unwrap_or_default()
+            )?;
+        }
+        comparisons.flush()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12}{:<20}{:<13}{:>12}{:>12}{:>12}", "problem", "configuration", "metric", "mean", "median", "iqr")?;
+
+        for row in &self.summaries {
+            writeln!(
+                f,
+                "{:<12}{:<20}{:<13}{:>12.6}{:>12.6}{:>12.6}",
+                row.problem,
+                row.configuration,
+                row.metric.name(),
+                row.mean,
+                row.median,
+                row.iqr
+            )?;
+        }
+
+        if !self.comparisons.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{:<12}{:<13}{:<41}{:>10}  better", "problem", "metric", "pair", "p-value")?;
+
+            for row in &self.comparisons {
+                let pair = format!("{} vs {}", row.first, row.second);
+                writeln!(
+                    f,
+                    "{:<12}{:<13}{:<41}{:>10.4}  {}",
+                    row.problem,
+                    row.metric.name(),
+                    pair,
+                    row.p_value,
+                    row.better.as_deref().unwrap_or("-")
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite(problems: Vec<ProblemConfig>, labels: &[&str]) -> Suite {
+        let algorithm = AlgorithmConfig { population_size: 8, ..AlgorithmConfig::default() };
+
+        Suite {
+            seeds: 2,
+            first_seed: 0,
+            problems,
+            configurations: labels
+                .iter()
+                .map(|label| Configuration {
+                    label: label.to_string(),
+                    algorithm: algorithm.clone(),
+                    operators: OperatorConfig::default(),
+                    termination: TerminationConfig { max_generations: 2, ..TerminationConfig::default() },
+                })
+                .collect(),
+            output: None,
+        }
+    }
+
+    /// A record measured with [`measure`]
+    fn measured(front: &[Vec<f64>], reference_front: &[Vec<f64>]) -> RunRecord {
+        let mut record = RunRecord {
+            problem: "problem".to_string(),
+            configuration: "configuration".to_string(),
+            seed: 0,
+            hypervolume: f64::NAN,
+            igd: f64::NAN,
+            front_size: front.len(),
+            evaluations: 0,
+            seconds: 0.0,
+        };
+        measure(&mut record, front, reference_front);
+        record
+    }
+
+    #[test]
+    fn measures_hypervolume_and_igd_on_a_known_front() {
+        let known = vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]];
+
+        // Boxes up to (1.1, 1.1) of widths 0.5, 0.5 and 0.1 and heights 0.1, 0.6 and 1.1
+        let exact = measured(&known, &known);
+        assert!((exact.hypervolume - 0.46).abs() < 1e-12, "{}", exact.hypervolume);
+        assert_eq!(exact.igd, 0.0);
+
+        // Both are taken after normalizing to the reference front, so scale and offset do not matter
+        let moved = known.iter().map(|p| vec![3.0 + 2.0 * p[0], -1.0 + 10.0 * p[1]]).collect::<Vec<_>>();
+        let scaled = measured(&moved, &moved);
+        assert!((scaled.hypervolume - 0.46).abs() < 1e-12, "{}", scaled.hypervolume);
+        assert!(scaled.igd.abs() < 1e-12, "{}", scaled.igd);
+
+        // Only the middle point: its own box, and the two ends are 0.5√2 away from it
+        let middle = measured(&[vec![0.5, 0.5]], &known);
+        assert!((middle.hypervolume - 0.36).abs() < 1e-12, "{}", middle.hypervolume);
+        assert!((middle.igd - 2.0_f64.sqrt() / 3.0).abs() < 1e-12, "{}", middle.igd);
+
+        // Points beyond the reference point add nothing
+        let beyond = measured(&[vec![2.0, 2.0]], &known);
+        assert_eq!(beyond.hypervolume, 0.0);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_empty_configuration_labels() {
+        assert!(suite(vec![ProblemConfig::Quadratic], &["a", "b"]).validate().is_ok());
+        assert!(suite(vec![ProblemConfig::Quadratic], &["a", "b", "a"]).validate().is_err());
+        assert!(suite(vec![ProblemConfig::Quadratic], &[""]).validate().is_err());
+    }
+
+    #[test]
+    fn keeps_runs_of_problems_with_the_same_name_apart() {
+        let zdt3 = ProblemConfig::named("zdt3").unwrap();
+        let suite = suite(vec![zdt3.clone(), ProblemConfig::Quadratic, zdt3], &["a", "b"]);
+        assert_eq!(suite.problem_labels(), ["zdt3#1", "quadratic", "zdt3#3"]);
+
+        let mut progress = Vec::new();
+        let report = suite.run(|run| progress.push(run.problem.clone())).unwrap();
+
+        // One call per run, in the order the runs finish
+        assert_eq!(progress, report.runs.iter().map(|run| run.problem.clone()).collect::<Vec<_>>());
+        assert_eq!(progress.len(), 3 * 2 * 2);
+
+        for label in suite.problem_labels() {
+            assert_eq!(report.runs.iter().filter(|run| run.problem == label).count(), 4);
+            assert_eq!(report.summaries.iter().filter(|row| row.problem == label).count(), 2 * Metric::ALL.len());
+            assert_eq!(report.comparisons.iter().filter(|row| row.problem == label).count(), Metric::ALL.len());
+        }
+    }
+
+    #[test]
+    fn quotes_labels_in_csv() {
+        assert_eq!(quoted("plain"), "\"plain\"");
+        assert_eq!(quoted("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,82 @@
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nsga2::distributed::{run_worker, WorkerOptions};
+use nsga2::problems::*;
+
+const USAGE: &str = "usage: nsga2-worker --connect HOST:PORT --problem quadratic|zdt3|rastrigin|sphere|ackley|rosenbrock|griewank|schwefel|dtlz1|dtlz2|series|series_hikita|series_parallel|bridge|overspeed|variable_series|knapsack|tsp|flow_shop|external [--reconnect-delay SECONDS] [--max-reconnects N]
+
+The master sends the parameters of its instance, such as the number of
+variables or, for external, the program to run on this machine.";
+
+fn main() -> ExitCode {
+    let mut connect = None;
+    let mut problem = None;
+    let mut options = WorkerOptions::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next();
+
+        let parsed = match (arg.as_str(), value) {
+            ("--connect", Some(value)) => {
+                connect = Some(value);
+                true
+            }
+            ("--problem", Some(value)) => {
+                problem = Some(value);
+                true
+            }
+            ("--reconnect-delay", Some(value)) => value
+                .parse()
+                .map(|seconds| options.reconnect_delay = Duration::from_secs_f64(seconds))
+                .is_ok(),
+            ("--max-reconnects", Some(value)) => value
+                .parse()
+                .map(|max| options.max_reconnects = Some(max))
+                .is_ok(),
+            _ => false,
+        };
+
+        if !parsed {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let (Some(connect), Some(problem)) = (connect, problem) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    let result = match problem.as_str() {
+        "quadratic" => run_worker::<quadratic::QuadraticProblem>(&connect, &problem, &options),
+        "zdt3" => run_worker::<thirty::Zdt3Params>(&connect, &problem, &options),
+        "rastrigin" => run_worker::<rastrigin::RastriginParams>(&connect, &problem, &options),
+        "sphere" | "ackley" | "rosenbrock" | "griewank" | "schwefel" => {
+            run_worker::<Arc<functions::FunctionInstance>>(&connect, &problem, &options)
+        }
+        "dtlz1" | "dtlz2" => run_worker::<Arc<dtlz::DtlzInstance>>(&connect, &problem, &options),
+        "series" | "series_hikita" | "series_parallel" | "bridge" | "overspeed" => {
+            run_worker::<Arc<series::SeriesInstance>>(&connect, &problem, &options)
+        }
+        "variable_series" => run_worker::<Arc<series::VariableSeriesInstance>>(&connect, &problem, &options),
+        "knapsack" => run_worker::<Arc<knapsack::KnapsackInstance>>(&connect, &problem, &options),
+        "tsp" => run_worker::<Arc<tsp::TspInstance>>(&connect, &problem, &options),
+        "flow_shop" => run_worker::<Arc<flowshop::FlowShopInstance>>(&connect, &problem, &options),
+        "external" => run_worker::<Arc<external::ExternalSpec>>(&connect, &problem, &options),
+        _ => {
+            eprintln!("unknown problem {:?}\n{}", problem, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("worker stopped: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::archive::ArchivePolicy;
+use crate::evaluation::FailurePolicy;
+use crate::initialization::Initialization;
+use crate::nsga2::Replacement;
+use crate::nsga3::Survival;
+
+/// Parameters for a run of any [`Algorithm`](crate::Algorithm): the population,
+/// termination, variation and evaluation settings they all share, plus the
+/// survival and replacement schemes only [`NSGA2`](crate::nsga2::NSGA2) uses.
+///
+/// Build one with [`Config::builder`], which validates every field before
+/// handing out a config, so a `Config` is always usable as-is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    population_size: usize,
+    max_generations: usize,
+    max_evaluations: Option<usize>,
+    time_limit: Option<Duration>,
+    mutation_rate: f64,
+    crossover_rate: f64,
+    tournament_size: usize,
+    initialization: Initialization,
+    survival: Survival,
+    replacement: Replacement,
+    archive: Option<ArchivePolicy>,
+    seed: Option<u64>,
+    failure_policy: FailurePolicy,
+    evaluation_retries: u32,
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    pub fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    pub fn max_generations(&self) -> usize {
+        self.max_generations
+    }
+
+    pub fn max_evaluations(&self) -> Option<usize> {
+        self.max_evaluations
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn crossover_rate(&self) -> f64 {
+        self.crossover_rate
+    }
+
+    pub fn tournament_size(&self) -> usize {
+        self.tournament_size
+    }
+
+    pub fn initialization(&self) -> Initialization {
+        self.initialization
+    }
+
+    pub fn survival(&self) -> Survival {
+        self.survival
+    }
+
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
+    }
+
+    pub fn archive(&self) -> Option<ArchivePolicy> {
+        self.archive
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn failure_policy(&self) -> FailurePolicy {
+        self.failure_policy
+    }
+
+    pub fn evaluation_retries(&self) -> u32 {
+        self.evaluation_retries
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        ConfigBuilder::default()
+            .build()
+            .expect("default configuration is valid")
+    }
+}
+
+/// Builder for [`Config`]. Every option starts out at a sensible default.
+#[derive(Clone, Debug)]
+pub struct ConfigBuilder {
+    population_size: usize,
+    max_generations: usize,
+    max_evaluations: Option<usize>,
+    time_limit: Option<Duration>,
+    mutation_rate: f64,
+    crossover_rate: f64,
+    tournament_size: usize,
+    initialization: Initialization,
+    survival: Survival,
+    replacement: Replacement,
+    archive: Option<ArchivePolicy>,
+    seed: Option<u64>,
+    failure_policy: FailurePolicy,
+    evaluation_retries: u32,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            population_size: 100,
+            max_generations: 1_000,
+            max_evaluations: None,
+            time_limit: None,
+            mutation_rate: 0.1,
+            crossover_rate: 0.9,
+            tournament_size: 2,
+            initialization: Initialization::default(),
+            survival: Survival::default(),
+            replacement: Replacement::default(),
+            archive: None,
+            seed: None,
+            failure_policy: FailurePolicy::default(),
+            evaluation_retries: 0,
+        }
+    }
+}
+
+impl ConfigBuilder {
+    /// Number of individuals kept between generations. Must be even and at least 2.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size;
+        self
+    }
+
+    pub fn max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = max_generations;
+        self
+    }
+
+    /// Stop after the generation in which this many evaluations have been spent.
+    pub fn max_evaluations(mut self, max_evaluations: usize) -> Self {
+        self.max_evaluations = Some(max_evaluations);
+        self
+    }
+
+    /// Stop after the generation in which this much wall-clock time has passed.
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Probability in `[0, 1]` that an offspring is mutated.
+    pub fn mutation_rate(mut self, mutation_rate: f64) -> Self {
+        self.mutation_rate = mutation_rate;
+        self
+    }
+
+    /// Probability in `[0, 1]` that two parents are recombined rather than copied.
+    pub fn crossover_rate(mut self, crossover_rate: f64) -> Self {
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Number of individuals competing in each selection tournament.
+    pub fn tournament_size(mut self, tournament_size: usize) -> Self {
+        self.tournament_size = tournament_size;
+        self
+    }
+
+    /// How the initial population is drawn, apart from any known solutions it starts with.
+    pub fn initialization(mut self, initialization: Initialization) -> Self {
+        self.initialization = initialization;
+        self
+    }
+
+    /// How NSGA-II truncates the front that overflows the next population.
+    /// [`Survival::ReferenceDirections`] turns the run into NSGA-III.
+    pub fn survival(mut self, survival: Survival) -> Self {
+        self.survival = survival;
+        self
+    }
+
+    /// Whether NSGA-II replaces the whole population every generation or a few
+    /// individuals at a time.
+    pub fn replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Keep the best solutions ever evaluated in an external archive, which
+    /// then replaces the final population's front as the result.
+    pub fn archive(mut self, policy: ArchivePolicy) -> Self {
+        self.archive = Some(policy);
+        self
+    }
+
+    /// Seed for the random number generator, making runs reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// What to do with candidates that still fail after all retries.
+    pub fn failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+
+    /// How many times a failed evaluation is repeated before the failure policy applies.
+    pub fn evaluation_retries(mut self, evaluation_retries: u32) -> Self {
+        self.evaluation_retries = evaluation_retries;
+        self
+    }
+
+    pub fn build(self) -> Result<Config, ConfigError> {
+        if self.population_size < 2 {
+            return Err(ConfigError::PopulationTooSmall(self.population_size));
+        }
+
+        // Parents are paired up two by two when producing offspring
+        if !self.population_size.is_multiple_of(2) {
+            return Err(ConfigError::OddPopulation(self.population_size));
+        }
+
+        check_probability("mutation_rate", self.mutation_rate)?;
+        check_probability("crossover_rate", self.crossover_rate)?;
+
+        if self.tournament_size < 1 || self.tournament_size > self.population_size {
+            return Err(ConfigError::InvalidTournamentSize(self.tournament_size));
+        }
+
+        if let Survival::ReferenceDirections { directions } = self.survival {
+            if directions.is_empty() {
+                return Err(ConfigError::NoReferenceDirections);
+            }
+        }
+
+        if let Replacement::SteadyState { offspring } = self.replacement {
+            if offspring < 1 || offspring > self.population_size {
+                return Err(ConfigError::InvalidOffspringCount(offspring));
+            }
+        }
+
+        match self.archive {
+            Some(ArchivePolicy::EpsilonDominance { epsilon }) if !(epsilon > 0.0 && epsilon.is_finite()) => {
+                return Err(ConfigError::InvalidEpsilon(epsilon));
+            }
+            Some(ArchivePolicy::Crowding { capacity: 0 }) => return Err(ConfigError::ArchiveTooSmall),
+            _ => {}
+        }
+
+        Ok(Config {
+            population_size: self.population_size,
+            max_generations: self.max_generations,
+            max_evaluations: self.max_evaluations,
+            time_limit: self.time_limit,
+            mutation_rate: self.mutation_rate,
+            crossover_rate: self.crossover_rate,
+            tournament_size: self.tournament_size,
+            initialization: self.initialization,
+            survival: self.survival,
+            replacement: self.replacement,
+            archive: self.archive,
+            seed: self.seed,
+            failure_policy: self.failure_policy,
+            evaluation_retries: self.evaluation_retries,
+        })
+    }
+}
+
+fn check_probability(name: &'static str, value: f64) -> Result<(), ConfigError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(ConfigError::RateOutOfRange { name, value })
+    }
+}
+
+/// Reasons a [`ConfigBuilder`] refuses to build.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    PopulationTooSmall(usize),
+    OddPopulation(usize),
+    RateOutOfRange { name: &'static str, value: f64 },
+    InvalidTournamentSize(usize),
+    NoReferenceDirections,
+    InvalidOffspringCount(usize),
+    InvalidEpsilon(f64),
+    ArchiveTooSmall,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::PopulationTooSmall(size) => {
+                write!(f, "population size must be at least 2, got {}", size)
+            }
+            ConfigError::OddPopulation(size) => {
+                write!(f, "population size must be even, got {}", size)
+            }
+            ConfigError::RateOutOfRange { name, value } => {
+                write!(f, "{} must be within [0, 1], got {}", name, value)
+            }
+            ConfigError::InvalidTournamentSize(size) => {
+                write!(f, "tournament size must be between 1 and the population size, got {}", size)
+            }
+            ConfigError::NoReferenceDirections => write!(f, "reference directions must not be empty"),
+            ConfigError::InvalidOffspringCount(count) => {
+                write!(f, "offspring per step must be between 1 and the population size, got {}", count)
+            }
+            ConfigError::InvalidEpsilon(epsilon) => {
+                write!(f, "archive epsilon must be positive and finite, got {}", epsilon)
+            }
+            ConfigError::ArchiveTooSmall => write!(f, "archive capacity must be at least 1"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directions::Directions;
+
+    /// The error of building the default settings with `settings` applied
+    fn rejected(settings: impl FnOnce(ConfigBuilder) -> ConfigBuilder) -> ConfigError {
+        settings(Config::builder()).build().unwrap_err()
+    }
+
+    #[test]
+    fn builds_the_defaults() {
+        let config = Config::builder().build().unwrap();
+        assert_eq!(config.population_size(), Config::default().population_size());
+    }
+
+    #[test]
+    fn rejects_every_invalid_setting() {
+        assert_eq!(rejected(|b| b.population_size(1)), ConfigError::PopulationTooSmall(1));
+        assert_eq!(rejected(|b| b.population_size(9)), ConfigError::OddPopulation(9));
+        assert_eq!(
+            rejected(|b| b.mutation_rate(1.5)),
+            ConfigError::RateOutOfRange { name: "mutation_rate", value: 1.5 }
+        );
+        assert_eq!(
+            rejected(|b| b.crossover_rate(-0.1)),
+            ConfigError::RateOutOfRange { name: "crossover_rate", value: -0.1 }
+        );
+        assert!(matches!(rejected(|b| b.mutation_rate(f64::NAN)), ConfigError::RateOutOfRange { .. }));
+        assert_eq!(rejected(|b| b.tournament_size(0)), ConfigError::InvalidTournamentSize(0));
+        assert_eq!(
+            rejected(|b| b.population_size(4).tournament_size(5)),
+            ConfigError::InvalidTournamentSize(5)
+        );
+        let directions = Directions::DasDennis { divisions: 0 };
+        assert_eq!(
+            rejected(|b| b.survival(Survival::ReferenceDirections { directions })),
+            ConfigError::NoReferenceDirections
+        );
+        assert_eq!(
+            rejected(|b| b.replacement(Replacement::SteadyState { offspring: 0 })),
+            ConfigError::InvalidOffspringCount(0)
+        );
+        assert_eq!(
+            rejected(|b| b.population_size(10).replacement(Replacement::SteadyState { offspring: 11 })),
+            ConfigError::InvalidOffspringCount(11)
+        );
+        assert_eq!(
+            rejected(|b| b.archive(ArchivePolicy::EpsilonDominance { epsilon: 0.0 })),
+            ConfigError::InvalidEpsilon(0.0)
+        );
+        assert!(matches!(
+            rejected(|b| b.archive(ArchivePolicy::EpsilonDominance { epsilon: f64::INFINITY })),
+            ConfigError::InvalidEpsilon(_)
+        ));
+        assert_eq!(rejected(|b| b.archive(ArchivePolicy::Crowding { capacity: 0 })), ConfigError::ArchiveTooSmall);
+    }
+
+    #[test]
+    fn accepts_the_edges_of_every_range() {
+        let config = Config::builder()
+            .population_size(2)
+            .mutation_rate(0.0)
+            .crossover_rate(1.0)
+            .tournament_size(2)
+            .replacement(Replacement::SteadyState { offspring: 2 })
+            .archive(ArchivePolicy::Crowding { capacity: 1 })
+            .build();
+
+        assert!(config.is_ok(), "{:?}", config);
+    }
+}
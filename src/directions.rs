@@ -0,0 +1,201 @@
+//! Reference directions on the unit simplex, for reference-point based
+//! selection and decomposition.
+
+use serde::{Deserialize, Serialize};
+
+/// Iterations spent spreading out an energy-based set.
+const ENERGY_ITERATIONS: usize = 500;
+
+/// A recipe for a set of reference directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Directions {
+    /// Uniform grid with `divisions` steps per objective, see [`das_dennis`]
+    DasDennis { divisions: usize },
+    /// Boundary grid plus a shrunken inner grid, see [`two_layer`]
+    TwoLayer { outer: usize, inner: usize },
+    /// Exactly `count` directions spread by minimizing energy, see [`riesz_energy`]
+    Energy { count: usize },
+}
+
+impl Directions {
+    pub fn generate(&self, objectives: usize) -> Vec<Vec<f64>> {
+        match *self {
+            Directions::DasDennis { divisions } => das_dennis(objectives, divisions),
+            Directions::TwoLayer { outer, inner } => two_layer(objectives, outer, inner),
+            Directions::Energy { count } => riesz_energy(objectives, count),
+        }
+    }
+
+    /// Whether the recipe leaves nothing to select by
+    pub fn is_empty(&self) -> bool {
+        matches!(
+            self,
+            Directions::DasDennis { divisions: 0 } | Directions::TwoLayer { outer: 0, .. } | Directions::Energy { count: 0 }
+        )
+    }
+}
+
+/// Every point of the unit simplex in `objectives` dimensions whose coordinates
+/// are multiples of `1 / divisions` (Das and Dennis, 1998).
+///
+/// There are `C(objectives + divisions - 1, divisions)` of them.
+pub fn das_dennis(objectives: usize, divisions: usize) -> Vec<Vec<f64>> {
+    let mut directions = Vec::new();
+
+    if objectives == 0 {
+        return directions;
+    }
+
+    if divisions == 0 {
+        directions.push(vec![1.0 / objectives as f64; objectives]);
+        return directions;
+    }
+
+    let mut current = vec![0; objectives];
+    compositions(&mut current, 0, divisions, &mut |parts| {
+        directions.push(parts.iter().map(|&part| part as f64 / divisions as f64).collect());
+    });
+
+    directions
+}
+
+/// Das–Dennis directions with `outer` divisions on the boundary, plus a second
+/// grid with `inner` divisions shrunk halfway towards the centre of the simplex
+/// (Deb and Jain, 2014).
+///
+/// With many objectives a single grid either has no interior points or too many
+/// points overall; two coarse layers cover both the boundary and the interior.
+pub fn two_layer(objectives: usize, outer: usize, inner: usize) -> Vec<Vec<f64>> {
+    let centre = 1.0 / objectives as f64;
+
+    let mut directions = das_dennis(objectives, outer);
+    directions.extend(
+        das_dennis(objectives, inner)
+            .into_iter()
+            .map(|direction| direction.into_iter().map(|w| 0.5 * w + 0.5 * centre).collect::<Vec<_>>()),
+    );
+
+    directions
+}
+
+/// `count` directions spread evenly over the simplex by minimizing their Riesz
+/// s-energy (Blank et al., 2020), for when no Das–Dennis grid has a usable size.
+///
+/// The result is deterministic: the points start from the farthest-apart subset
+/// of a fine Das–Dennis grid, corners first, and are then pushed apart by
+/// projected gradient steps.
+pub fn riesz_energy(objectives: usize, count: usize) -> Vec<Vec<f64>> {
+    if objectives <= 1 || count <= 1 {
+        return das_dennis(objectives, 0).into_iter().cycle().take(count).collect();
+    }
+
+    let mut points = farthest_subset(&das_dennis(objectives, divisions_for(objectives, count)), count);
+    let s = objectives as f64 + 1.0;
+    let mut step = 1.0 / count as f64;
+
+    for _ in 0..ENERGY_ITERATIONS {
+        // Repulsion on every point, restricted to the plane of the simplex
+        let mut forces = vec![vec![0.0; objectives]; count];
+
+        for i in 0..count {
+            for j in i + 1..count {
+                let difference = points[i].iter().zip(&points[j]).map(|(a, b)| a - b).collect::<Vec<_>>();
+                let distance = difference.iter().map(|d| d * d).sum::<f64>().sqrt().max(1e-12);
+                let magnitude = distance.powf(-(s + 2.0));
+
+                for (m, d) in difference.iter().enumerate() {
+                    forces[i][m] += d * magnitude;
+                    forces[j][m] -= d * magnitude;
+                }
+            }
+        }
+
+        for force in &mut forces {
+            let mean = force.iter().sum::<f64>() / objectives as f64;
+            force.iter_mut().for_each(|f| *f -= mean);
+        }
+
+        let largest = forces
+            .iter()
+            .map(|force| force.iter().map(|f| f * f).sum::<f64>().sqrt())
+            .fold(0.0, f64::max);
+
+        if largest <= f64::EPSILON {
+            break;
+        }
+
+        for (point, force) in points.iter_mut().zip(&forces) {
+            for (w, f) in point.iter_mut().zip(force) {
+                *w = (*w + step * f / largest).max(0.0);
+            }
+
+            let total = point.iter().sum::<f64>();
+            point.iter_mut().for_each(|w| *w /= total);
+        }
+
+        step *= 0.99;
+    }
+
+    points
+}
+
+/// Smallest number of Das–Dennis divisions giving at least `count` directions.
+pub(crate) fn divisions_for(objectives: usize, count: usize) -> usize {
+    let mut divisions = 1;
+
+    // C(objectives + divisions - 1, divisions), built up one factor at a time
+    let size = |divisions: usize| (1..=divisions).fold(1.0, |size, k| size * (objectives + k - 1) as f64 / k as f64);
+
+    while size(divisions) < count as f64 {
+        divisions += 1;
+    }
+
+    divisions
+}
+
+/// Greedily pick `count` of `candidates`, each time the one farthest from all
+/// picked so far, starting with the corners of the simplex.
+fn farthest_subset(candidates: &[Vec<f64>], count: usize) -> Vec<Vec<f64>> {
+    let mut picked: Vec<Vec<f64>> = candidates
+        .iter()
+        .filter(|candidate| candidate.contains(&1.0))
+        .take(count)
+        .cloned()
+        .collect();
+
+    let distance = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>();
+    let mut nearest = candidates
+        .iter()
+        .map(|candidate| picked.iter().map(|p| distance(candidate, p)).fold(f64::INFINITY, f64::min))
+        .collect::<Vec<_>>();
+
+    while picked.len() < count {
+        let Some((next, _)) = nearest.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)) else {
+            break;
+        };
+
+        let chosen = candidates[next].clone();
+        for (candidate, gap) in candidates.iter().zip(&mut nearest) {
+            *gap = gap.min(distance(candidate, &chosen));
+        }
+
+        picked.push(chosen);
+    }
+
+    picked
+}
+
+/// Visit every way of writing `left` as an ordered sum of `parts.len() - position` non-negative integers.
+fn compositions(parts: &mut [usize], position: usize, left: usize, visit: &mut impl FnMut(&[usize])) {
+    if position == parts.len() - 1 {
+        parts[position] = left;
+        visit(parts);
+        return;
+    }
+
+    for part in (0..=left).rev() {
+        parts[position] = part;
+        compositions(parts, position + 1, left - part, visit);
+    }
+}
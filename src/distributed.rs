@@ -0,0 +1,16 @@
+//! Evaluation of candidates on remote workers.
+//!
+//! A [`Master`](master::Master) listens for TCP connections and acts as the
+//! engine's [`Evaluator`](crate::evaluation::Evaluator). Workers started with
+//! [`run_worker`](worker::run_worker) connect to it and announce the problem
+//! they host. The master answers with the parameters of its instance, which the
+//! worker checks and builds once, and then sends one genome at a time to
+//! evaluate. Messages are JSON documents, each preceded by its length as a
+//! 4-byte big-endian integer.
+
+pub mod master;
+pub mod protocol;
+pub mod worker;
+
+pub use master::{Master, MasterOptions};
+pub use worker::{run_worker, WorkerOptions};
@@ -0,0 +1,241 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::distributed::protocol::{read_frame, write_frame, MasterMessage, WorkerMessage};
+use crate::evaluation::{Evaluation, EvaluationError, Evaluator};
+use crate::solution::Portable;
+
+/// Tuning knobs for a [`Master`].
+#[derive(Clone, Debug)]
+pub struct MasterOptions {
+    /// How long a worker may take on one job before it is considered lost
+    /// and the job handed to another worker. `None` waits forever.
+    pub job_timeout: Option<Duration>,
+    /// How long a newly connected worker has to introduce itself and build
+    /// the problem instance.
+    pub handshake_timeout: Duration,
+}
+
+impl Default for MasterOptions {
+    fn default() -> Self {
+        Self {
+            job_timeout: None,
+            handshake_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+type Outcome = Result<Evaluation, EvaluationError>;
+
+/// What the master tells every worker before its first job
+struct Handshake {
+    /// Catalog name the worker must announce
+    problem: String,
+    params: Value,
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<(u64, Value)>,
+    results: HashMap<u64, Outcome>,
+    workers: usize,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+/// Hands out evaluations to connected workers, one job per worker at a time.
+///
+/// Workers receive the parameters of the problem instance once, when they
+/// connect, and then only the genome of each candidate. Idle workers take the
+/// next job from a shared queue, which balances the load between fast and slow
+/// machines. Jobs held by a worker that disconnects or exceeds the job timeout
+/// go back to the front of the queue. A batch only completes once every job
+/// has a result, so evaluation blocks while no worker is connected.
+pub struct Master<P> {
+    problem: P,
+    shared: Arc<Shared>,
+    local_addr: SocketAddr,
+    next_id: u64,
+}
+
+impl<P: Portable> Master<P> {
+    /// Listen on `addr` for workers hosting the problem of the instance `problem`.
+    pub fn bind(addr: impl ToSocketAddrs, problem: P, options: MasterOptions) -> io::Result<Self> {
+        let handshake = Handshake {
+            problem: problem.name().to_string(),
+            params: serde_json::to_value(problem.params())?,
+        };
+
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared::default());
+
+        let accept_shared = Arc::clone(&shared);
+        thread::spawn(move || accept(listener, accept_shared, Arc::new(handshake), options));
+
+        Ok(Self {
+            problem,
+            shared,
+            local_addr,
+            next_id: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of workers currently connected.
+    pub fn workers(&self) -> usize {
+        self.shared.state.lock().unwrap().workers
+    }
+
+    /// Block until at least `count` workers are connected, or `timeout` passes.
+    /// Returns whether enough workers showed up.
+    pub fn wait_for_workers(&self, count: usize, timeout: Duration) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        let (state, _) = self
+            .shared
+            .changed
+            .wait_timeout_while(state, timeout, |state| state.workers < count)
+            .unwrap();
+        state.workers >= count
+    }
+}
+
+impl<P: Portable> Evaluator<P::Solution> for Master<P> {
+    fn evaluate(&mut self, batch: &[P::Solution]) -> Vec<Result<Evaluation, EvaluationError>> {
+        let first_id = self.next_id;
+        self.next_id += batch.len() as u64;
+
+        let mut state = self.shared.state.lock().unwrap();
+
+        for (id, solution) in (first_id..).zip(batch) {
+            match serde_json::to_value(self.problem.genome(solution)) {
+                Ok(value) => state.queue.push_back((id, value)),
+                Err(e) => {
+                    state.results.insert(id, Err(EvaluationError::Failed(format!("could not encode job: {}", e))));
+                }
+            }
+        }
+
+        self.shared.changed.notify_all();
+
+        let ids = first_id..self.next_id;
+        let mut state = self
+            .shared
+            .changed
+            .wait_while(state, |state| ids.clone().any(|id| !state.results.contains_key(&id)))
+            .unwrap();
+
+        ids.map(|id| state.results.remove(&id).expect("every job has a result"))
+            .collect()
+    }
+}
+
+impl<P> Drop for Master<P> {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.changed.notify_all();
+
+        // Wake the accept loop so it notices the shutdown
+        let _ = TcpStream::connect(self.local_addr);
+    }
+}
+
+fn accept(listener: TcpListener, shared: Arc<Shared>, handshake: Arc<Handshake>, options: MasterOptions) {
+    for stream in listener.incoming() {
+        if shared.state.lock().unwrap().shutdown {
+            return;
+        }
+
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        let shared = Arc::clone(&shared);
+        let handshake = Arc::clone(&handshake);
+        let options = options.clone();
+        thread::spawn(move || serve(stream, shared, &handshake, &options));
+    }
+}
+
+/// Feed jobs to a single worker until it disconnects or the master shuts down.
+fn serve(mut stream: TcpStream, shared: Arc<Shared>, handshake: &Handshake, options: &MasterOptions) {
+    if stream.set_nodelay(true).is_err() || stream.set_read_timeout(Some(options.handshake_timeout)).is_err() {
+        return;
+    }
+
+    match read_frame(&mut stream) {
+        Ok(WorkerMessage::Hello { problem }) if problem == handshake.problem => {}
+        _ => return,
+    }
+
+    // Workers that cannot host the instance hang up instead of answering
+    let ready = write_frame(&mut stream, &MasterMessage::<_, Value>::Problem { params: &handshake.params })
+        .and_then(|_| read_frame(&mut stream));
+    if !matches!(ready, Ok(WorkerMessage::Ready)) {
+        return;
+    }
+
+    if stream.set_read_timeout(options.job_timeout).is_err() {
+        return;
+    }
+
+    {
+        let mut state = shared.state.lock().unwrap();
+        state.workers += 1;
+        shared.changed.notify_all();
+    }
+
+    loop {
+        let job = {
+            let state = shared.state.lock().unwrap();
+            let mut state = shared
+                .changed
+                .wait_while(state, |state| state.queue.is_empty() && !state.shutdown)
+                .unwrap();
+
+            if state.shutdown {
+                state.workers -= 1;
+                drop(state);
+                let _ = write_frame(&mut stream, &MasterMessage::<Value, Value>::Shutdown);
+                return;
+            }
+
+            state.queue.pop_front().expect("queue is not empty")
+        };
+
+        let (id, genome) = job;
+
+        let reply = write_frame(&mut stream, &MasterMessage::<Value, _>::Job { id, genome: &genome })
+            .and_then(|_| read_frame(&mut stream));
+
+        let mut state = shared.state.lock().unwrap();
+
+        match reply {
+            Ok(WorkerMessage::Result { id: reply_id, outcome }) if reply_id == id => {
+                state.results.insert(id, outcome);
+                shared.changed.notify_all();
+            }
+            _ => {
+                // Lost or confused worker: give the job to someone else
+                state.queue.push_front((id, genome));
+                state.workers -= 1;
+                shared.changed.notify_all();
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::{self, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::evaluation::{Evaluation, EvaluationError};
+
+/// Largest message either side accepts, guarding against corrupt length prefixes
+pub const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// Messages sent from the master to a worker.
+///
+/// `P` are the parameters of the problem instance and `G` the genome of its
+/// solutions, see [`Portable`](crate::solution::Portable).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MasterMessage<P, G> {
+    /// Answer to a matching [`WorkerMessage::Hello`]: the instance every job belongs to.
+    Problem { params: P },
+    Job { id: u64, genome: G },
+    Shutdown,
+}
+
+/// Messages sent from a worker to the master.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkerMessage {
+    /// First message on every connection, naming the problem the worker hosts.
+    Hello { problem: String },
+    /// The worker built the instance from [`MasterMessage::Problem`] and takes jobs.
+    Ready,
+    Result { id: u64, outcome: Result<Evaluation, EvaluationError> },
+}
+
+pub fn write_frame<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+
+    if body.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "message too large"));
+    }
+
+    // One write per frame, so the length prefix does not sit in a packet of its own
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&body);
+
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+pub fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> io::Result<T> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+
+    if length > MAX_FRAME_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
@@ -0,0 +1,124 @@
+use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::distributed::protocol::{read_frame, write_frame, MasterMessage, WorkerMessage};
+use crate::evaluation::{evaluate, EvaluationError};
+use crate::solution::Portable;
+
+/// Tuning knobs for [`run_worker`].
+#[derive(Clone, Debug)]
+pub struct WorkerOptions {
+    /// Pause between attempts to (re)connect to the master.
+    pub reconnect_delay: Duration,
+    /// Consecutive failed connection attempts before giving up. `None` retries forever.
+    pub max_reconnects: Option<usize>,
+}
+
+impl Default for WorkerOptions {
+    fn default() -> Self {
+        Self {
+            reconnect_delay: Duration::from_secs(1),
+            max_reconnects: Some(30),
+        }
+    }
+}
+
+/// Connect to the master at `addr` and evaluate candidates of the instance it
+/// hands out, which must be an instance of the catalog problem `problem`,
+/// until the master shuts down.
+///
+/// Lost connections are re-established after [`WorkerOptions::reconnect_delay`];
+/// the master reassigns whatever job was in flight. Parameters that do not
+/// describe a valid instance of `problem` stop the worker with an
+/// [`InvalidData`](io::ErrorKind::InvalidData) error right away.
+pub fn run_worker<P: Portable>(addr: impl ToSocketAddrs, problem: &str, options: &WorkerOptions) -> io::Result<()> {
+    let addrs = addr.to_socket_addrs()?.collect::<Vec<_>>();
+    let mut failures = 0;
+
+    loop {
+        let error = match TcpStream::connect(&addrs[..]) {
+            Ok(stream) => match serve::<P>(stream, problem, &mut failures) {
+                Ok(()) => return Ok(()),
+                Err(Ended::Refused(e)) => return Err(e),
+                Err(Ended::Lost(e)) => e,
+            },
+            Err(e) => e,
+        };
+
+        failures += 1;
+
+        if options.max_reconnects.is_some_and(|max| failures > max) {
+            return Err(error);
+        }
+
+        thread::sleep(options.reconnect_delay);
+    }
+}
+
+/// Why a connection ended other than by the master asking us to stop
+enum Ended {
+    /// The connection failed and is worth re-establishing
+    Lost(io::Error),
+    /// The master wants an instance we cannot host, and would ask again
+    Refused(io::Error),
+}
+
+impl From<io::Error> for Ended {
+    fn from(error: io::Error) -> Self {
+        Ended::Lost(error)
+    }
+}
+
+/// Serve jobs on one connection. Returns `Ok` only when the master asked us to stop.
+/// The failure count is reset once the master has accepted us and sent a job,
+/// so a master that keeps rejecting the handshake still makes the worker give up.
+fn serve<P: Portable>(mut stream: TcpStream, problem: &str, failures: &mut usize) -> Result<(), Ended> {
+    stream.set_nodelay(true)?;
+    write_frame(&mut stream, &WorkerMessage::Hello { problem: problem.to_string() })?;
+
+    // Read as plain JSON, so that parameters or genomes of another shape are
+    // reported as such rather than taken for a broken connection
+    let instance = match read_frame::<_, MasterMessage<Value, Value>>(&mut stream)? {
+        MasterMessage::Problem { params } => host::<P>(params, problem)
+            .map_err(|reason| Ended::Refused(io::Error::new(io::ErrorKind::InvalidData, reason)))?,
+        MasterMessage::Shutdown => return Ok(()),
+        MasterMessage::Job { .. } => return Err(unexpected("a job before the problem").into()),
+    };
+    write_frame(&mut stream, &WorkerMessage::Ready)?;
+
+    loop {
+        match read_frame::<_, MasterMessage<Value, Value>>(&mut stream)? {
+            MasterMessage::Job { id, genome } => {
+                *failures = 0;
+                let outcome = serde_json::from_value(genome)
+                    .map_err(|e| e.to_string())
+                    .and_then(|genome| instance.solution(genome))
+                    .map_err(|reason| EvaluationError::Failed(format!("invalid job: {}", reason)))
+                    .and_then(|solution| evaluate(&solution));
+                write_frame(&mut stream, &WorkerMessage::Result { id, outcome })?;
+            }
+            MasterMessage::Problem { .. } => return Err(unexpected("a second problem").into()),
+            MasterMessage::Shutdown => return Ok(()),
+        }
+    }
+}
+
+/// The instance described by `params`, if it is one of the catalog problem `problem`
+fn host<P: Portable>(params: Value, problem: &str) -> Result<P, String> {
+    let params = serde_json::from_value(params).map_err(|e| format!("invalid parameters for {}: {}", problem, e))?;
+    let instance = P::from_params(params).map_err(|reason| format!("invalid parameters for {}: {}", problem, reason))?;
+
+    if instance.name() != problem {
+        return Err(format!("the master sent parameters of {}, but this worker hosts {}", instance.name(), problem));
+    }
+
+    Ok(instance)
+}
+
+fn unexpected(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("the master sent {}", message))
+}
@@ -0,0 +1,18 @@
+/// Pareto dominance for minimization: `a` is no worse than `b` in every
+/// objective and strictly better in at least one.
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    let mut better_in_any = false;
+
+    for (a_value, b_value) in a.iter().zip(b.iter()) {
+        // If a is worse in any objective, it does not dominate
+        if a_value > b_value {
+            return false;
+        }
+
+        if a_value < b_value {
+            better_in_any = true;
+        }
+    }
+
+    better_in_any
+}
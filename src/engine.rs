@@ -0,0 +1,411 @@
+//! Machinery shared by the algorithms: sampling the initial population,
+//! evaluating with retries and failure policies, variation, termination and
+//! per-generation history.
+
+use std::mem;
+use std::rc::Rc;
+use std::time::Instant;
+
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::evaluation::{Evaluation, EvaluationError, Evaluator, FailurePolicy, LocalEvaluator, RunError, RunStatistics};
+use crate::history::{GenerationRecord, Observer, RunHistory};
+use crate::individual::Individual;
+use crate::initialization::{Initialization, UnitPoints};
+use crate::nsga2::{non_dominated_sort, Sampler};
+use crate::solution::{Problem, Solution};
+
+/// Upper bound on replacements per batch under [`FailurePolicy::Resample`],
+/// as a multiple of the population size, so a problem that always fails
+/// cannot stall the run forever.
+pub(crate) const RESAMPLE_LIMIT_FACTOR: usize = 10;
+
+/// Builds the solution at a point of the unit hypercube
+type Decode<S> = Box<dyn Fn(&[f64]) -> Option<S>>;
+
+/// Maps points of the unit hypercube to solutions, for the [`Initialization`]
+/// strategies other than random.
+pub(crate) struct Encoding<S> {
+    dimension: usize,
+    decode: Decode<S>,
+}
+
+impl<S> Encoding<S> {
+    /// The encoding of `problem`, if it has one
+    pub(crate) fn of<P: Problem<Solution = S> + 'static>(problem: &Rc<P>) -> Option<Self> {
+        let dimension = problem.unit_dimension()?;
+        let problem = Rc::clone(problem);
+
+        Some(Self { dimension, decode: Box::new(move |point| problem.solution_at(point)) })
+    }
+}
+
+/// Public only so that the sealed trait behind [`Build`](crate::algorithm::Build)
+/// can name it; the module keeps it out of reach of other crates.
+pub struct Engine<S: Solution> {
+    pub(crate) config: Config,
+    sampler: Sampler<S>,
+    encoding: Option<Encoding<S>>,
+    /// Known solutions the initial population starts with
+    initial_solutions: Vec<S>,
+    /// Initial solutions taken so far in this run
+    seeded: usize,
+    /// Points for the initial population, continued by every call of
+    /// [`Engine::initial_population`] in this run
+    points: Option<UnitPoints>,
+    evaluator: Box<dyn Evaluator<S>>,
+    pub(crate) statistics: RunStatistics,
+    pub(crate) history: RunHistory,
+    /// Best solutions of every evaluated batch, if the config asks for an archive
+    pub(crate) archive: Option<Archive<S>>,
+    observers: Vec<Box<dyn Observer<S>>>,
+    pub(crate) rng: StdRng,
+    started: Instant,
+    /// Generations completed since the start of the run
+    pub(crate) generation: usize,
+    /// Learned from the first successful evaluation, for penalizing failures
+    pub(crate) objective_count: usize,
+    /// Best feasible individual ever evaluated, for single-objective problems
+    pub(crate) best: Option<Individual<S>>,
+}
+
+impl<S: Solution> Engine<S> {
+    pub(crate) fn new(config: Config, sampler: Sampler<S>) -> Self {
+        let rng = match config.seed() {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let archive = config.archive().map(Archive::new);
+
+        Self {
+            config,
+            sampler,
+            encoding: None,
+            initial_solutions: Vec::new(),
+            seeded: 0,
+            points: None,
+            evaluator: Box::new(LocalEvaluator),
+            statistics: RunStatistics::default(),
+            history: RunHistory::default(),
+            archive,
+            observers: Vec::new(),
+            rng,
+            started: Instant::now(),
+            generation: 0,
+            objective_count: 0,
+            best: None,
+        }
+    }
+
+    pub(crate) fn set_encoding(&mut self, encoding: Option<Encoding<S>>) {
+        self.encoding = encoding;
+    }
+
+    pub(crate) fn set_initial_solutions(&mut self, solutions: Vec<S>) {
+        self.initial_solutions = solutions;
+    }
+
+    pub(crate) fn set_evaluator(&mut self, evaluator: Box<dyn Evaluator<S>>) {
+        self.evaluator = evaluator;
+    }
+
+    pub(crate) fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.observers.push(observer);
+    }
+
+    /// Forget the previous run and start the clock.
+    pub(crate) fn start(&mut self) {
+        self.statistics = RunStatistics::default();
+        self.history = RunHistory::default();
+        self.started = Instant::now();
+        self.generation = 0;
+        self.objective_count = 0;
+        self.best = None;
+        self.seeded = 0;
+        self.points = None;
+
+        if let Some(archive) = &mut self.archive {
+            archive.clear();
+        }
+    }
+
+    /// Whether the run should end after the current generation.
+    pub(crate) fn finished(&self) -> bool {
+        self.generation >= self.config.max_generations() || self.exhausted()
+    }
+
+    /// Whether the evaluation or time budget is spent, which may end a generation early.
+    pub(crate) fn exhausted(&self) -> bool {
+        self.config.max_evaluations().is_some_and(|max| self.statistics.evaluations >= max)
+            || self.config.time_limit().is_some_and(|limit| self.started.elapsed() >= limit)
+    }
+
+    /// Add the state of `population`, which sorts into `fronts`, to the history
+    /// as the current generation and show it to the observers.
+    pub(crate) fn record(&mut self, population: &[Individual<S>], fronts: &[Vec<usize>]) {
+        let mut record = GenerationRecord::new(
+            self.generation,
+            self.statistics.evaluations,
+            self.started.elapsed(),
+            population,
+            fronts.len(),
+            fronts.first().map_or(0, Vec::len),
+        );
+        record.best = self.best.as_ref().map(|best| best.objectives[0]);
+        self.history.push(record);
+
+        if let Some(record) = self.history.last() {
+            for observer in &mut self.observers {
+                observer.observe(record, population);
+            }
+        }
+    }
+
+    /// Evaluate the known initial solutions and draw the rest of `size`
+    /// individuals as configured, resampling failures if configured. Later
+    /// calls in the same run go on with the initial solutions not taken yet
+    /// and the points after the ones drawn so far.
+    pub(crate) fn initial_population(&mut self, size: usize) -> Result<Vec<Individual<S>>, RunError> {
+        let initialization = self.config.initialization();
+        if self.points.is_none() {
+            self.points = match &self.encoding {
+                Some(encoding) => UnitPoints::new(initialization, encoding.dimension, &mut self.rng),
+                None if initialization == Initialization::Random => None,
+                None => return Err(RunError::NoUnitEncoding(initialization)),
+            };
+        }
+
+        let mut population = Vec::with_capacity(size);
+        let mut candidates = self.initial_solutions.iter().skip(self.seeded).take(size).cloned().collect::<Vec<_>>();
+        self.seeded += candidates.len();
+        let mut resampled = 0;
+
+        while population.len() < size {
+            let missing = size - population.len() - candidates.len();
+            candidates.extend(self.draw(missing)?);
+
+            let count = candidates.len();
+            let evaluated = self.evaluate(mem::take(&mut candidates))?;
+            resampled += count - evaluated.len();
+            population.extend(evaluated);
+
+            if resampled > RESAMPLE_LIMIT_FACTOR * size {
+                return Err(RunError::ResampleLimit(resampled));
+            }
+        }
+
+        // Penalized individuals could not learn the objective count from their own evaluation
+        let objective_count = population
+            .iter()
+            .map(|individual| individual.objectives.len())
+            .find(|&count| count > 0)
+            .ok_or(RunError::NoValidEvaluations)?;
+
+        for individual in population.iter_mut().filter(|i| i.objectives.is_empty()) {
+            individual.objectives = vec![f64::INFINITY; objective_count];
+        }
+
+        self.objective_count = objective_count;
+
+        // Opposition-based initialization draws twice as many as it keeps
+        if population.len() > size {
+            population = self.fittest(population, size);
+        }
+
+        Ok(population)
+    }
+
+    /// `count` new candidates, from the unit points through the encoding if the
+    /// initialization draws any and from the sampler otherwise.
+    fn draw(&mut self, count: usize) -> Result<Vec<S>, RunError> {
+        let initialization = self.config.initialization();
+
+        match (&mut self.points, &self.encoding) {
+            (Some(points), Some(encoding)) => points
+                .batch(count, &mut self.rng)
+                .ok_or(RunError::SequenceExhausted(initialization))?
+                .iter()
+                .map(|point| (encoding.decode)(point))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(RunError::NoUnitEncoding(initialization)),
+            _ => Ok((0..count).map(|_| (self.sampler)(&mut self.rng)).collect()),
+        }
+    }
+
+    /// The best `count` of `population`: feasible ones first, then by
+    /// non-dominated front, with ties broken at random.
+    fn fittest(&mut self, mut population: Vec<Individual<S>>, count: usize) -> Vec<Individual<S>> {
+        population.shuffle(&mut self.rng);
+
+        let mut ranks = vec![0; population.len()];
+        for (rank, front) in non_dominated_sort(&population).into_iter().enumerate() {
+            for i in front {
+                ranks[i] = rank;
+            }
+        }
+
+        let mut ranked = population.into_iter().zip(ranks).collect::<Vec<_>>();
+        ranked.sort_by_key(|(individual, rank)| (!individual.feasible, *rank));
+        ranked.into_iter().take(count).map(|(individual, _)| individual).collect()
+    }
+
+    /// A child of two parents, recombined and mutated according to the configured rates.
+    pub(crate) fn breed(&mut self, parent1: &S, parent2: &S) -> S {
+        let dist = Uniform::new(0.0, 1.0);
+
+        let mut child = if self.rng.sample(dist) < self.config.crossover_rate() {
+            parent1.crossover(parent2, &mut self.rng)
+        } else {
+            parent1.clone()
+        };
+
+        if self.rng.sample(dist) < self.config.mutation_rate() {
+            child.mutate(&mut self.rng);
+        }
+
+        child
+    }
+
+    /// `count` evaluated children of consecutive pairs of `mating_pool`, a new
+    /// pair starting every `stride` parents. Children discarded for resampling
+    /// are replaced by breeding the next pairs, wrapping around the pool.
+    pub(crate) fn offspring(
+        &mut self,
+        mating_pool: &[S],
+        count: usize,
+        stride: usize,
+    ) -> Result<Vec<Individual<S>>, RunError> {
+        let mut offspring = Vec::with_capacity(count);
+        let mut resampled = 0;
+
+        let mut i = 0;
+        while offspring.len() < count {
+            let missing = count - offspring.len();
+            let mut children = Vec::with_capacity(missing);
+
+            for _ in 0..missing {
+                let parent1 = &mating_pool[i % mating_pool.len()];
+                let parent2 = &mating_pool[(i + 1) % mating_pool.len()];
+                i += stride;
+
+                children.push(self.breed(parent1, parent2));
+            }
+
+            let evaluated = self.evaluate(children)?;
+            resampled += missing - evaluated.len();
+            offspring.extend(evaluated);
+
+            if resampled > RESAMPLE_LIMIT_FACTOR * self.config.population_size() {
+                return Err(RunError::ResampleLimit(resampled));
+            }
+        }
+
+        Ok(offspring)
+    }
+
+    /// Evaluate a batch of solutions, retrying and applying the failure policy as configured.
+    /// Solutions discarded for resampling are left out of the result.
+    pub(crate) fn evaluate(&mut self, candidates: Vec<S>) -> Result<Vec<Individual<S>>, RunError> {
+        Ok(self.evaluate_each(candidates)?.into_iter().flatten().collect())
+    }
+
+    /// Like [`Engine::evaluate`], but keeps the order of `candidates`, with
+    /// `None` in place of solutions discarded for resampling.
+    pub(crate) fn evaluate_each(&mut self, candidates: Vec<S>) -> Result<Vec<Option<Individual<S>>>, RunError> {
+        let mut outcomes: Vec<Result<Evaluation, EvaluationError>> =
+            vec![Err(EvaluationError::Failed("not evaluated".to_string())); candidates.len()];
+        let mut pending = (0..candidates.len()).collect::<Vec<_>>();
+
+        for attempt in 0..=self.config.evaluation_retries() {
+            if pending.is_empty() {
+                break;
+            }
+
+            if attempt > 0 {
+                self.statistics.retries += pending.len();
+            }
+
+            self.statistics.evaluations += pending.len();
+
+            let results = if pending.len() == candidates.len() {
+                self.evaluator.evaluate(&candidates)
+            } else {
+                let batch = pending.iter().map(|&i| candidates[i].clone()).collect::<Vec<_>>();
+                self.evaluator.evaluate(&batch)
+            };
+
+            for (&i, result) in pending.iter().zip(results) {
+                outcomes[i] = result.and_then(|evaluation| {
+                    if evaluation.objectives.iter().any(|value| value.is_nan()) {
+                        Err(EvaluationError::NotANumber(evaluation.objectives))
+                    } else {
+                        Ok(evaluation)
+                    }
+                });
+            }
+
+            pending.retain(|&i| outcomes[i].is_err());
+            self.statistics.failures += pending.len();
+        }
+
+        let mut individuals = Vec::with_capacity(candidates.len());
+
+        for (solution, outcome) in candidates.into_iter().zip(outcomes) {
+            let error = match outcome {
+                Ok(Evaluation { objectives, feasible }) => {
+                    individuals.push(Some(Individual { solution, objectives, feasible }));
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            match self.config.failure_policy() {
+                FailurePolicy::Resample => {
+                    self.statistics.resampled += 1;
+                    individuals.push(None);
+                }
+                FailurePolicy::Penalize => {
+                    self.statistics.penalized += 1;
+
+                    individuals.push(Some(Individual {
+                        solution,
+                        objectives: vec![f64::INFINITY; self.objective_count],
+                        feasible: false,
+                    }));
+                }
+                FailurePolicy::Abort => return Err(RunError::Aborted(error)),
+            }
+        }
+
+        if let Some(archive) = &mut self.archive {
+            for individual in individuals.iter().flatten() {
+                archive.offer(individual);
+            }
+        }
+
+        for individual in individuals.iter().flatten() {
+            self.offer_best(individual);
+        }
+
+        Ok(individuals)
+    }
+
+    /// Keep `individual` as the best so far if the problem has a single
+    /// objective and it is feasible and better than the current best.
+    fn offer_best(&mut self, individual: &Individual<S>) {
+        let [value] = individual.objectives[..] else {
+            return;
+        };
+
+        if individual.feasible
+            && value.is_finite()
+            && self.best.as_ref().is_none_or(|best| value < best.objectives[0])
+        {
+            self.best = Some(individual.clone());
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::initialization::Initialization;
+use crate::solution::Solution;
+
+/// The outcome of successfully evaluating a candidate solution.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub objectives: Vec<f64>,
+    pub feasible: bool,
+}
+
+/// Computes objective values for batches of candidates on behalf of the engine.
+pub trait Evaluator<S> {
+    /// Evaluate every solution in `batch`, returning one result per solution in the same order.
+    fn evaluate(&mut self, batch: &[S]) -> Vec<Result<Evaluation, EvaluationError>>;
+}
+
+/// Evaluates candidates one after another on the calling thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalEvaluator;
+
+impl<S: Solution> Evaluator<S> for LocalEvaluator {
+    fn evaluate(&mut self, batch: &[S]) -> Vec<Result<Evaluation, EvaluationError>> {
+        batch.iter().map(evaluate).collect()
+    }
+}
+
+/// Evaluate a single solution with its own objective and constraint functions.
+pub fn evaluate<S: Solution>(solution: &S) -> Result<Evaluation, EvaluationError> {
+    let objectives = solution.fitness()?;
+    let feasible = solution.feasible();
+    Ok(Evaluation { objectives, feasible })
+}
+
+/// Why evaluating a candidate solution failed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EvaluationError {
+    /// The objective function reported an error, e.g. a crashed simulator.
+    Failed(String),
+    /// The evaluation did not finish within the allotted time.
+    Timeout(Duration),
+    /// At least one of the returned objective values is NaN.
+    NotANumber(Vec<f64>),
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluationError::Failed(reason) => write!(f, "evaluation failed: {}", reason),
+            EvaluationError::Timeout(limit) => write!(f, "evaluation timed out after {:?}", limit),
+            EvaluationError::NotANumber(objectives) => {
+                write!(f, "evaluation returned NaN objectives: {:?}", objectives)
+            }
+        }
+    }
+}
+
+impl Error for EvaluationError {}
+
+/// What the engine does with a candidate whose evaluation keeps failing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Throw the candidate away and generate a replacement in its place.
+    Resample,
+    /// Keep the candidate, but as infeasible and worst possible in every objective.
+    Penalize,
+    /// Stop the run and report the error.
+    #[default]
+    Abort,
+}
+
+/// Counters collected over a single run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunStatistics {
+    /// Calls made to the objective function, including retries.
+    pub evaluations: usize,
+    /// Calls that returned an error or NaN.
+    pub failures: usize,
+    /// Calls made to retry a failed evaluation.
+    pub retries: usize,
+    /// Candidates discarded and replaced under [`FailurePolicy::Resample`].
+    pub resampled: usize,
+    /// Candidates kept as worst under [`FailurePolicy::Penalize`].
+    pub penalized: usize,
+}
+
+/// Reasons a run ended early.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RunError {
+    /// An evaluation failed under [`FailurePolicy::Abort`].
+    Aborted(EvaluationError),
+    /// More candidates were resampled in a single batch than allowed.
+    ResampleLimit(usize),
+    /// Not a single member of the initial population could be evaluated.
+    NoValidEvaluations,
+    /// A single-objective algorithm was given a problem with this many objectives.
+    NotSingleObjective(usize),
+    /// The initialization strategy needs a problem that builds solutions from
+    /// points of the unit hypercube, which this one does not.
+    NoUnitEncoding(Initialization),
+    /// The sequence of the initialization strategy has no points left.
+    SequenceExhausted(Initialization),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Aborted(error) => write!(f, "run aborted: {}", error),
+            RunError::ResampleLimit(count) => {
+                write!(f, "gave up after resampling {} failed candidates", count)
+            }
+            RunError::NoValidEvaluations => {
+                write!(f, "no member of the initial population could be evaluated")
+            }
+            RunError::NotSingleObjective(count) => {
+                write!(f, "algorithm needs a single objective, but the problem has {}", count)
+            }
+            RunError::NoUnitEncoding(initialization) => write!(
+                f,
+                "{} initialization needs a problem whose solutions can be built from points of the unit hypercube",
+                initialization.name()
+            ),
+            RunError::SequenceExhausted(initialization) => {
+                write!(f, "{} initialization ran out of distinct points", initialization.name())
+            }
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RunError::Aborted(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,894 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::algorithm::{Algorithm, Build};
+use crate::archive::ArchivePolicy;
+use crate::config::{Config, ConfigError};
+use crate::distributed::{Master, MasterOptions};
+use crate::evaluation::{Evaluator, FailurePolicy, RunError, RunStatistics};
+use crate::ga::{Ga, GaParams};
+use crate::history::RunHistory;
+use crate::individual::Individual;
+use crate::initialization::Initialization;
+use crate::live::LiveView;
+use crate::moead::{Moead, MoeadParams};
+use crate::nsga2::{Replacement, NSGA2};
+use crate::nsga3::Survival;
+use crate::output::{read_solutions, write_csv, write_json};
+use crate::plot::{self, Coloring, PlotOptions};
+use crate::problems::dtlz::{Dtlz, DtlzInstance, DtlzParams};
+use crate::problems::external::{ExternalSpec, Format};
+use crate::problems::flowshop::{FlowShopInstance, FlowShopParams};
+use crate::problems::functions::{Function, FunctionInstance, FunctionParams};
+use crate::problems::knapsack::{KnapsackInstance, KnapsackParams};
+use crate::problems::rastrigin::RastriginParams;
+use crate::problems::series::{
+    SeriesInstance, SeriesParams, SeriesSystem, VariableSeriesInstance, VariableSeriesParams,
+};
+use crate::problems::thirty::Zdt3Params;
+use crate::problems::tsp::{TspInstance, TspParams};
+use crate::problems::*;
+use crate::sms_emoa::SmsEmoa;
+use crate::solution::{Portable, Solution};
+use crate::spea2::{Spea2, Spea2Params};
+
+/// A complete description of one optimization run, loadable from TOML or JSON.
+///
+/// Every section but `problem` may be left out to use the defaults.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experiment {
+    pub problem: ProblemConfig,
+    #[serde(default)]
+    pub algorithm: AlgorithmConfig,
+    #[serde(default)]
+    pub operators: OperatorConfig,
+    #[serde(default)]
+    pub termination: TerminationConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+    /// Evaluate candidates on `nsga2-worker` processes instead of the current thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workers: Option<WorkersConfig>,
+}
+
+/// Which problem to solve, by its [`CATALOG`] name, with its parameters.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum ProblemConfig {
+    #[default]
+    Quadratic,
+    Zdt3(Zdt3Params),
+    Rastrigin(RastriginParams),
+    Function(FunctionParams),
+    Dtlz(DtlzParams),
+    Series(SeriesParams),
+    VariableSeries(VariableSeriesParams),
+    Knapsack(KnapsackParams),
+    Tsp(TspParams),
+    FlowShop(FlowShopParams),
+    External(ExternalConfig),
+}
+
+impl ProblemConfig {
+    /// Default parameters of the catalog problem called `name`
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "quadratic" => Some(ProblemConfig::Quadratic),
+            "zdt3" => Some(ProblemConfig::Zdt3(Zdt3Params::default())),
+            "rastrigin" => Some(ProblemConfig::Rastrigin(RastriginParams::default())),
+            "sphere" | "ackley" | "rosenbrock" | "griewank" | "schwefel" => Function::ALL
+                .into_iter()
+                .find(|function| function.name() == name)
+                .map(|function| ProblemConfig::Function(FunctionParams { function, ..FunctionParams::default() })),
+            "dtlz1" | "dtlz2" => Dtlz::ALL
+                .into_iter()
+                .find(|function| function.name() == name)
+                .map(|function| ProblemConfig::Dtlz(DtlzParams::preset(function))),
+            "series" => Some(ProblemConfig::Series(SeriesParams::baseline())),
+            "series_hikita" => Some(ProblemConfig::Series(SeriesParams::preset(SeriesSystem::Series))),
+            "series_parallel" | "bridge" | "overspeed" => SeriesSystem::ALL
+                .into_iter()
+                .find(|system| system.name() == name)
+                .map(|system| ProblemConfig::Series(SeriesParams::preset(system))),
+            "variable_series" => Some(ProblemConfig::VariableSeries(VariableSeriesParams::default())),
+            "knapsack" => Some(ProblemConfig::Knapsack(KnapsackParams::default())),
+            "tsp" => Some(ProblemConfig::Tsp(TspParams::default())),
+            "flow_shop" => Some(ProblemConfig::FlowShop(FlowShopParams::default())),
+            "external" => Some(ProblemConfig::External(ExternalConfig::default())),
+            _ => None,
+        }
+    }
+
+    /// Known Pareto front of the problem, if it has one in closed form
+    pub fn reference_front(&self) -> Option<Vec<Vec<f64>>> {
+        match self {
+            ProblemConfig::Zdt3(_) => Some(thirty::pareto_front(1_000)),
+            ProblemConfig::Dtlz(params) => Some(params.pareto_front(1_000)),
+            ProblemConfig::Rastrigin(_) | ProblemConfig::Function(_) => Some(vec![vec![0.0]]),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProblemConfig::Quadratic => "quadratic",
+            ProblemConfig::Zdt3(_) => "zdt3",
+            ProblemConfig::Rastrigin(_) => "rastrigin",
+            ProblemConfig::Function(params) => params.function.name(),
+            ProblemConfig::Dtlz(params) => params.function.name(),
+            ProblemConfig::Series(params) => params.name(),
+            ProblemConfig::VariableSeries(_) => "variable_series",
+            ProblemConfig::Knapsack(_) => "knapsack",
+            ProblemConfig::Tsp(_) => "tsp",
+            ProblemConfig::FlowShop(_) => "flow_shop",
+            ProblemConfig::External(_) => "external",
+        }
+    }
+}
+
+/// File-friendly form of [`ExternalSpec`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalConfig {
+    pub program: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub bounds: Vec<(f64, f64)>,
+    pub objectives: usize,
+    #[serde(default)]
+    pub format: Format,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<f64>,
+}
+
+impl ExternalConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bounds.is_empty() {
+            return Err("external problem needs bounds".to_string());
+        }
+        for (i, &(low, high)) in self.bounds.iter().enumerate() {
+            if !low.is_finite() || !high.is_finite() || low > high {
+                return Err(format!("invalid bounds {}:{} of variable {}", low, high, i + 1));
+            }
+        }
+        if self.objectives == 0 {
+            return Err("external problem needs at least 1 objective".to_string());
+        }
+        if let Some(timeout) = self.timeout_seconds {
+            if !timeout.is_finite() || timeout <= 0.0 {
+                return Err(format!("timeout must be a positive number of seconds, got {}", timeout));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn spec(&self) -> ExternalSpec {
+        let mut spec = ExternalSpec::new(&self.program, self.bounds.clone(), self.objectives);
+        spec.args = self.args.clone();
+        spec.format = self.format;
+
+        if let Some(timeout) = self.timeout_seconds {
+            spec.timeout = Duration::from_secs_f64(timeout);
+        }
+
+        spec
+    }
+}
+
+/// Which optimizer runs the experiment, with its own parameters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum Method {
+    /// NSGA-II, or NSGA-III with reference-direction survival
+    #[default]
+    Nsga2,
+    /// Decomposition into scalar subproblems
+    Moead(MoeadParams),
+    /// Strength fitness with an archive
+    Spea2(Spea2Params),
+    /// Steady state with hypervolume-based reduction
+    SmsEmoa,
+    /// Single-objective genetic algorithm with elitism
+    Ga(GaParams),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlgorithmConfig {
+    pub method: Method,
+    pub population_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub failure_policy: FailurePolicy,
+    pub evaluation_retries: u32,
+    pub survival: Survival,
+    pub replacement: Replacement,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchivePolicy>,
+    pub initialization: Initialization,
+    /// Results of an earlier run to start the initial population with, as
+    /// written by `write_json` or `write_csv`, or a `results.txt` listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_solutions: Option<PathBuf>,
+}
+
+impl Default for AlgorithmConfig {
+    fn default() -> Self {
+        let defaults = Config::default();
+
+        Self {
+            method: Method::default(),
+            population_size: defaults.population_size(),
+            seed: defaults.seed(),
+            failure_policy: defaults.failure_policy(),
+            evaluation_retries: defaults.evaluation_retries(),
+            survival: defaults.survival(),
+            replacement: defaults.replacement(),
+            archive: defaults.archive(),
+            initialization: defaults.initialization(),
+            initial_solutions: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperatorConfig {
+    pub mutation_rate: f64,
+    pub crossover_rate: f64,
+    pub tournament_size: usize,
+}
+
+impl Default for OperatorConfig {
+    fn default() -> Self {
+        let defaults = Config::default();
+
+        Self {
+            mutation_rate: defaults.mutation_rate(),
+            crossover_rate: defaults.crossover_rate(),
+            tournament_size: defaults.tournament_size(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TerminationConfig {
+    pub max_generations: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_evaluations: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_limit_seconds: Option<f64>,
+}
+
+impl Default for TerminationConfig {
+    fn default() -> Self {
+        let defaults = Config::default();
+
+        Self {
+            max_generations: defaults.max_generations(),
+            max_evaluations: defaults.max_evaluations(),
+            time_limit_seconds: defaults.time_limit().map(|limit| limit.as_secs_f64()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Directory receiving the results and the resolved experiment. Nothing is
+    /// written when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+    pub format: OutputFormat,
+    /// Write the whole final population instead of only the Pareto front
+    pub population: bool,
+    /// Also draw the written individuals as an SVG plot
+    pub plot: bool,
+}
+
+/// Where a [`Master`] listens for workers, and how many it waits for.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkersConfig {
+    /// Address to listen on, such as `0.0.0.0:7878`
+    pub listen: String,
+    /// Workers that must connect before the run starts
+    #[serde(default = "default_min_workers")]
+    pub min_workers: usize,
+    /// How long to wait for them
+    #[serde(default = "default_wait_seconds")]
+    pub wait_seconds: f64,
+    /// How long a worker may take on one job before it is handed to another
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_timeout_seconds: Option<f64>,
+}
+
+fn default_min_workers() -> usize {
+    1
+}
+
+fn default_wait_seconds() -> f64 {
+    60.0
+}
+
+impl WorkersConfig {
+    pub fn new(listen: impl Into<String>) -> Self {
+        Self {
+            listen: listen.into(),
+            min_workers: default_min_workers(),
+            wait_seconds: default_wait_seconds(),
+            job_timeout_seconds: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.listen.is_empty() {
+            return Err("workers need an address to listen on".to_string());
+        }
+        if !self.wait_seconds.is_finite() || self.wait_seconds < 0.0 {
+            return Err(format!("wait must be a non-negative number of seconds, got {}", self.wait_seconds));
+        }
+        if let Some(timeout) = self.job_timeout_seconds {
+            if !timeout.is_finite() || timeout <= 0.0 {
+                return Err(format!("job timeout must be a positive number of seconds, got {}", timeout));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Listen for workers hosting `problem` and wait for enough of them to connect
+    fn master<P: Portable>(&self, problem: P) -> Result<Master<P>, ExperimentError> {
+        let options = MasterOptions {
+            job_timeout: self.job_timeout_seconds.map(Duration::from_secs_f64),
+            ..MasterOptions::default()
+        };
+        let master = Master::bind(&self.listen, problem, options)
+            .map_err(|e| ExperimentError::Workers(format!("cannot listen on {}: {}", self.listen, e)))?;
+
+        if !master.wait_for_workers(self.min_workers, Duration::from_secs_f64(self.wait_seconds)) {
+            return Err(ExperimentError::Workers(format!(
+                "only {} of {} workers connected to {} within {} seconds",
+                master.workers(),
+                self.min_workers,
+                master.local_addr(),
+                self.wait_seconds
+            )));
+        }
+
+        Ok(master)
+    }
+}
+
+/// The final state of a finished experiment, with solutions in serialized form
+/// so that results of different problems share one type.
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub statistics: RunStatistics,
+    pub history: RunHistory,
+    pub population: Vec<Individual<Value>>,
+    /// Members of the external archive, if the experiment kept one
+    pub archive: Option<Vec<Individual<Value>>>,
+    /// Best feasible solution ever evaluated, if the problem has a single objective
+    pub best: Option<Individual<Value>>,
+    /// The solutions the algorithm reports, see [`Algorithm::result`]
+    pub result: Vec<Individual<Value>>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    /// The reported result, see [`Algorithm::result`]
+    pub fn pareto_front(&self) -> Vec<&Individual<Value>> {
+        self.result.iter().collect()
+    }
+}
+
+impl Experiment {
+    /// Read an experiment from a `.toml` or `.json` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ExperimentError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ExperimentError::Io(path.to_path_buf(), e))?;
+
+        let experiment: Experiment = FileFormat::of(path)?.parse(&text)?;
+
+        experiment.validate()?;
+        Ok(experiment)
+    }
+
+    /// Write the experiment as `.toml` or `.json`, depending on the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ExperimentError> {
+        let path = path.as_ref();
+
+        let text = FileFormat::of(path)?.render(self)?;
+
+        fs::write(path, text).map_err(|e| ExperimentError::Io(path.to_path_buf(), e))
+    }
+
+    /// Check the settings without running anything.
+    pub fn validate(&self) -> Result<(), ExperimentError> {
+        self.config()?;
+
+        match &self.algorithm.method {
+            Method::Moead(params) => params.validate().map_err(ExperimentError::Algorithm)?,
+            Method::Spea2(params) => params.validate().map_err(ExperimentError::Algorithm)?,
+            Method::Ga(params) => params.validate(self.algorithm.population_size).map_err(ExperimentError::Algorithm)?,
+            Method::Nsga2 | Method::SmsEmoa => {}
+        }
+
+        if let Some(workers) = &self.workers {
+            workers.validate().map_err(ExperimentError::Workers)?;
+        }
+
+        match &self.problem {
+            ProblemConfig::Series(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::VariableSeries(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Knapsack(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Zdt3(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Rastrigin(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Function(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Dtlz(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Tsp(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::FlowShop(params) => params.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::External(external) => external.validate().map_err(ExperimentError::Problem),
+            ProblemConfig::Quadratic => Ok(()),
+        }
+    }
+
+    /// Fill in everything left to chance, so that saving the experiment
+    /// afterwards records exactly what was run.
+    pub fn resolve(&mut self) {
+        if self.algorithm.seed.is_none() {
+            self.algorithm.seed = Some(rand::thread_rng().gen());
+        }
+    }
+
+    /// The settings shared by every algorithm, validated
+    pub fn config(&self) -> Result<Config, ConfigError> {
+        let mut builder = Config::builder()
+            .population_size(self.algorithm.population_size)
+            .failure_policy(self.algorithm.failure_policy)
+            .evaluation_retries(self.algorithm.evaluation_retries)
+            .survival(self.algorithm.survival)
+            .replacement(self.algorithm.replacement)
+            .initialization(self.algorithm.initialization)
+            .mutation_rate(self.operators.mutation_rate)
+            .crossover_rate(self.operators.crossover_rate)
+            .tournament_size(self.operators.tournament_size)
+            .max_generations(self.termination.max_generations);
+
+        if let Some(seed) = self.algorithm.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(archive) = self.algorithm.archive {
+            builder = builder.archive(archive);
+        }
+        if let Some(max_evaluations) = self.termination.max_evaluations {
+            builder = builder.max_evaluations(max_evaluations);
+        }
+        if let Some(time_limit) = self.termination.time_limit_seconds {
+            builder = builder.time_limit(Duration::from_secs_f64(time_limit));
+        }
+
+        builder.build()
+    }
+
+    pub fn run(&self) -> Result<Outcome, ExperimentError> {
+        self.run_with(None)
+    }
+
+    /// Like [`Experiment::run`], showing progress in `live_view` if given.
+    pub fn run_with(&self, live_view: Option<LiveView>) -> Result<Outcome, ExperimentError> {
+        self.validate()?;
+
+        match &self.problem {
+            ProblemConfig::Quadratic => self.run_problem(quadratic::QuadraticProblem, live_view),
+            ProblemConfig::Zdt3(params) => self.run_problem(params.clone(), live_view),
+            ProblemConfig::Rastrigin(params) => self.run_problem(params.clone(), live_view),
+            ProblemConfig::Function(params) => {
+                self.run_problem(Arc::new(FunctionInstance::new(params.clone())), live_view)
+            }
+            ProblemConfig::Dtlz(params) => self.run_problem(Arc::new(DtlzInstance::new(params.clone())), live_view),
+            ProblemConfig::Series(params) => {
+                self.run_problem(Arc::new(SeriesInstance::new(params.clone())), live_view)
+            }
+            ProblemConfig::VariableSeries(params) => {
+                self.run_problem(Arc::new(VariableSeriesInstance::new(params.clone())), live_view)
+            }
+            ProblemConfig::Knapsack(params) => {
+                self.run_problem(Arc::new(KnapsackInstance::new(params.clone())), live_view)
+            }
+            ProblemConfig::Tsp(params) => self.run_problem(Arc::new(TspInstance::new(params.clone())), live_view),
+            ProblemConfig::FlowShop(params) => {
+                self.run_problem(Arc::new(FlowShopInstance::new(params.clone())), live_view)
+            }
+            ProblemConfig::External(external) => self.run_problem(Arc::new(external.spec()), live_view),
+        }
+    }
+
+    /// The solutions in the `initial_solutions` file, if there is one, read
+    /// as variables and checked against `problem`
+    fn initial_solutions<P: Portable>(&self, problem: &P) -> Result<Vec<P::Solution>, ExperimentError> {
+        let Some(path) = &self.algorithm.initial_solutions else {
+            return Ok(Vec::new());
+        };
+
+        let text = fs::read_to_string(path).map_err(|e| ExperimentError::Io(path.clone(), e))?;
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let invalid = |message: String| ExperimentError::Solutions(path.clone(), message);
+
+        read_solutions(&text, extension)
+            .map_err(invalid)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                serde_json::from_value(value)
+                    .map_err(|e| e.to_string())
+                    .and_then(|genome| problem.solution(genome))
+                    .map_err(|e| invalid(format!("solution {}: {}", i + 1, e)))
+            })
+            .collect()
+    }
+
+    /// Write the outcome, its per-generation history and this experiment into the
+    /// configured output directory, if there is one.
+    pub fn write_results(&self, outcome: &Outcome) -> Result<(), ExperimentError> {
+        let Some(directory) = &self.output.directory else {
+            return Ok(());
+        };
+
+        fs::create_dir_all(directory).map_err(|e| ExperimentError::Io(directory.clone(), e))?;
+        self.save(directory.join("experiment.toml"))?;
+
+        let (name, individuals) = if self.output.population {
+            ("population", outcome.population.iter().collect())
+        } else {
+            ("front", outcome.pareto_front())
+        };
+
+        let path = match self.output.format {
+            OutputFormat::Csv => directory.join(format!("{}.csv", name)),
+            OutputFormat::Json => directory.join(format!("{}.json", name)),
+        };
+
+        let write = || -> io::Result<()> {
+            let mut file = BufWriter::new(File::create(&path)?);
+
+            match self.output.format {
+                OutputFormat::Csv => write_csv(&mut file, &individuals)?,
+                OutputFormat::Json => write_json(&mut file, &individuals)?,
+            }
+
+            file.flush()
+        };
+
+        write().map_err(|e| ExperimentError::Io(path.clone(), e))?;
+
+        if self.output.plot {
+            let path = directory.join(format!("{}.svg", name));
+            let svg = self.plot(&individuals);
+
+            fs::write(&path, svg).map_err(|e| ExperimentError::Io(path.clone(), e))?;
+        }
+
+        let path = directory.join("history.csv");
+        let write = || -> io::Result<()> {
+            let mut file = BufWriter::new(File::create(&path)?);
+            outcome.history.write_csv(&mut file)?;
+            file.flush()
+        };
+
+        write().map_err(|e| ExperimentError::Io(path.clone(), e))
+    }
+
+    /// A scatter plot against the known front for two objectives, parallel coordinates otherwise.
+    fn plot(&self, individuals: &[&Individual<Value>]) -> String {
+        let options = PlotOptions {
+            title: self.problem.name().to_string(),
+            coloring: if self.output.population { Coloring::Rank } else { Coloring::Feasibility },
+            reference_front: self.problem.reference_front(),
+            ..PlotOptions::default()
+        };
+
+        match individuals.first().map(|i| i.objectives.len()) {
+            Some(2) => plot::scatter(individuals, &options),
+            _ => plot::parallel_coordinates(individuals, &options),
+        }
+    }
+}
+
+impl Experiment {
+    fn run_problem<P: Portable + Clone + 'static>(
+        &self,
+        problem: P,
+        live_view: Option<LiveView>,
+    ) -> Result<Outcome, ExperimentError>
+    where
+        P::Solution: Serialize + 'static,
+    {
+        let initial = self.initial_solutions(&problem)?;
+        let config = self.config()?;
+
+        let evaluator: Option<Box<dyn Evaluator<P::Solution>>> = match &self.workers {
+            Some(workers) => Some(Box::new(workers.master(problem.clone())?)),
+            None => None,
+        };
+
+        let started = Instant::now();
+
+        let mut algorithm: Box<dyn Algorithm<P::Solution>> = match self.algorithm.method {
+            Method::Nsga2 => Box::new(prepare(NSGA2::with_problem(config, problem), initial, evaluator)),
+            Method::Moead(params) => {
+                Box::new(prepare(Moead::with_problem(config, problem).with_params(params), initial, evaluator))
+            }
+            Method::Spea2(params) => {
+                Box::new(prepare(Spea2::with_problem(config, problem).with_params(params), initial, evaluator))
+            }
+            Method::SmsEmoa => Box::new(prepare(SmsEmoa::with_problem(config, problem), initial, evaluator)),
+            Method::Ga(params) => {
+                Box::new(prepare(Ga::with_problem(config, problem).with_params(params), initial, evaluator))
+            }
+        };
+
+        if let Some(live_view) = live_view {
+            algorithm.add_observer(Box::new(live_view));
+        }
+
+        let statistics = algorithm.run()?;
+        let elapsed = started.elapsed();
+
+        let population = serialize(algorithm.population())?;
+        let archive = algorithm.archive().map(|archive| serialize(archive.members())).transpose()?;
+        let best = serialize(algorithm.best())?.pop();
+        let result = serialize(algorithm.result())?;
+
+        Ok(Outcome {
+            statistics,
+            history: algorithm.history().clone(),
+            population,
+            archive,
+            best,
+            result,
+            elapsed,
+        })
+    }
+}
+
+/// `algorithm` starting with `initial` and evaluating on `evaluator`, if given
+fn prepare<S: Solution, A: Build<S>>(algorithm: A, initial: Vec<S>, evaluator: Option<Box<dyn Evaluator<S>>>) -> A {
+    let mut algorithm = algorithm.with_initial_solutions(initial);
+
+    if let Some(evaluator) = evaluator {
+        algorithm.engine_mut().set_evaluator(evaluator);
+    }
+
+    algorithm
+}
+
+/// `individuals` with their solutions turned into JSON values
+fn serialize<'a, S: Serialize + 'a>(
+    individuals: impl IntoIterator<Item = &'a Individual<S>>,
+) -> Result<Vec<Individual<Value>>, ExperimentError> {
+    individuals
+        .into_iter()
+        .map(|individual| {
+            Ok(Individual {
+                solution: serde_json::to_value(&individual.solution)?,
+                objectives: individual.objectives.clone(),
+                feasible: individual.feasible,
+            })
+        })
+        .collect::<Result<_, serde_json::Error>>()
+        .map_err(|e| ExperimentError::Parse(e.to_string()))
+}
+
+/// Settings file formats, chosen by file extension
+pub(crate) enum FileFormat {
+    Toml,
+    Json,
+}
+
+impl FileFormat {
+    pub(crate) fn of(path: &Path) -> Result<Self, ExperimentError> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(FileFormat::Toml),
+            Some("json") => Ok(FileFormat::Json),
+            _ => Err(ExperimentError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+
+    pub(crate) fn parse<T: DeserializeOwned>(&self, text: &str) -> Result<T, ExperimentError> {
+        match self {
+            FileFormat::Toml => toml::from_str(text).map_err(|e| ExperimentError::Parse(e.to_string())),
+            FileFormat::Json => serde_json::from_str(text).map_err(|e| ExperimentError::Parse(e.to_string())),
+        }
+    }
+
+    pub(crate) fn render<T: Serialize>(&self, value: &T) -> Result<String, ExperimentError> {
+        match self {
+            FileFormat::Toml => toml::to_string_pretty(value).map_err(|e| ExperimentError::Parse(e.to_string())),
+            FileFormat::Json => serde_json::to_string_pretty(value).map_err(|e| ExperimentError::Parse(e.to_string())),
+        }
+    }
+}
+
+/// Reasons an experiment could not be loaded, run or saved.
+#[derive(Debug)]
+pub enum ExperimentError {
+    Io(PathBuf, io::Error),
+    UnknownFormat(PathBuf),
+    Parse(String),
+    Config(ConfigError),
+    Algorithm(String),
+    Problem(String),
+    /// A file of initial solutions that could not be read as solutions of the problem
+    Solutions(PathBuf, String),
+    /// Invalid worker settings, or workers that did not show up
+    Workers(String),
+    Run(RunError),
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExperimentError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            ExperimentError::UnknownFormat(path) => {
+                write!(f, "{}: expected a .toml or .json file", path.display())
+            }
+            ExperimentError::Parse(message) => write!(f, "invalid experiment: {}", message),
+            ExperimentError::Config(error) => write!(f, "invalid algorithm settings: {}", error),
+            ExperimentError::Algorithm(message) => write!(f, "invalid algorithm settings: {}", message),
+            ExperimentError::Problem(message) => write!(f, "invalid problem: {}", message),
+            ExperimentError::Solutions(path, message) => write!(f, "{}: {}", path.display(), message),
+            ExperimentError::Workers(message) => write!(f, "workers: {}", message),
+            ExperimentError::Run(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for ExperimentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExperimentError::Io(_, error) => Some(error),
+            ExperimentError::Config(error) => Some(error),
+            ExperimentError::Run(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConfigError> for ExperimentError {
+    fn from(error: ConfigError) -> Self {
+        ExperimentError::Config(error)
+    }
+}
+
+impl From<RunError> for ExperimentError {
+    fn from(error: RunError) -> Self {
+        ExperimentError::Run(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn external(bounds: Vec<(f64, f64)>, objectives: usize) -> Experiment {
+        Experiment {
+            problem: ProblemConfig::External(ExternalConfig {
+                program: PathBuf::from("evaluate"),
+                bounds,
+                objectives,
+                ..ExternalConfig::default()
+            }),
+            ..Experiment::default()
+        }
+    }
+
+    #[test]
+    fn accepts_valid_external_bounds() {
+        assert!(external(vec![(0.0, 1.0), (-2.0, -2.0)], 2).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_external_problems() {
+        for experiment in [
+            external(Vec::new(), 2),
+            external(vec![(1.0, 0.0)], 2),
+            external(vec![(0.0, f64::INFINITY)], 2),
+            external(vec![(f64::NAN, 1.0)], 2),
+            external(vec![(0.0, 1.0)], 0),
+        ] {
+            assert!(matches!(experiment.validate(), Err(ExperimentError::Problem(_))));
+        }
+    }
+
+    #[test]
+    fn reads_worker_settings_and_rejects_invalid_ones() {
+        let text = "[problem]\nname = \"zdt3\"\n\n[workers]\nlisten = \"0.0.0.0:7878\"\n";
+        let experiment: Experiment = FileFormat::Toml.parse(text).unwrap();
+        assert_eq!(experiment.workers, Some(WorkersConfig::new("0.0.0.0:7878")));
+        assert!(experiment.validate().is_ok());
+
+        for workers in [
+            WorkersConfig::new(""),
+            WorkersConfig { wait_seconds: -1.0, ..WorkersConfig::new("0.0.0.0:7878") },
+            WorkersConfig { job_timeout_seconds: Some(0.0), ..WorkersConfig::new("0.0.0.0:7878") },
+        ] {
+            let experiment = Experiment { workers: Some(workers), ..experiment.clone() };
+            assert!(matches!(experiment.validate(), Err(ExperimentError::Workers(_))));
+        }
+    }
+
+    /// A short run of `problem` seeded with the solutions in `seeds`, a file named `name`
+    fn seeded(problem: ProblemConfig, name: &str, seeds: &str) -> Result<Outcome, ExperimentError> {
+        let path = std::env::temp_dir().join(format!("nsga2-{}-{}", std::process::id(), name));
+        fs::write(&path, seeds).unwrap();
+
+        let experiment = Experiment {
+            problem,
+            algorithm: AlgorithmConfig {
+                population_size: 8,
+                seed: Some(0),
+                initial_solutions: Some(path.clone()),
+                ..AlgorithmConfig::default()
+            },
+            termination: TerminationConfig { max_generations: 2, ..TerminationConfig::default() },
+            ..Experiment::default()
+        };
+
+        let outcome = experiment.run();
+        fs::remove_file(path).unwrap();
+        outcome
+    }
+
+    #[test]
+    fn rebinds_seeds_written_for_another_instance() {
+        let data = vec![0.5; 30];
+        let thirty = serde_json::json!([{ "objectives": [0.5, 0.5], "feasible": true, "solution": { "data": data } }]);
+        let seeds = thirty.to_string();
+
+        assert!(seeded(ProblemConfig::Zdt3(Zdt3Params::default()), "zdt3.json", &seeds).is_ok());
+
+        let wider = ProblemConfig::Zdt3(Zdt3Params { variables: 100 });
+        assert!(matches!(seeded(wider, "zdt3.json", &seeds), Err(ExperimentError::Solutions(..))));
+
+        let rastrigin = ProblemConfig::Rastrigin(RastriginParams { dimension: 10 });
+        assert!(matches!(seeded(rastrigin, "rastrigin.json", &seeds), Err(ExperimentError::Solutions(..))));
+    }
+
+    #[test]
+    fn rejects_seeds_outside_the_space_of_the_instance() {
+        // A subsystem of a sixth stage, which the default five-stage system lacks
+        let gene = serde_json::json!({ "reals": [0.8], "integers": [2], "categories": [5] });
+        let seeds = serde_json::json!([{ "genome": [gene] }]).to_string();
+        let problem = ProblemConfig::VariableSeries(VariableSeriesParams::default());
+
+        let error = seeded(problem, "variable_series.json", &seeds).unwrap_err();
+        assert!(error.to_string().contains("solution 1: "), "{}", error);
+
+        let series = ProblemConfig::Series(SeriesParams::default());
+        let seeds = "r: [0.8, 0.8, 0.8], n: [2, 2, 2] == [0, 0]\n";
+        assert!(matches!(seeded(series, "series.txt", seeds), Err(ExperimentError::Solutions(..))));
+    }
+}
@@ -0,0 +1,180 @@
+//! A generational genetic algorithm for single-objective problems, ranking by
+//! objective value rather than by Pareto dominance, and carrying the best
+//! individuals over to the next generation unchanged.
+
+use std::cmp::Ordering;
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::{Algorithm, WithEngine};
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::engine::Engine;
+use crate::evaluation::{RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::{pareto_front, Individual};
+use crate::nsga2::non_dominated_sort;
+use crate::solution::Solution;
+
+/// Settings specific to [`Ga`]; everything else comes from [`Config`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GaParams {
+    /// Number of best individuals copied into the next generation unchanged
+    pub elites: usize,
+}
+
+impl Default for GaParams {
+    fn default() -> Self {
+        Self { elites: 1 }
+    }
+}
+
+impl GaParams {
+    pub fn validate(&self, population_size: usize) -> Result<(), String> {
+        if self.elites >= population_size {
+            return Err(format!(
+                "elites must be fewer than the population size {}, got {}",
+                population_size, self.elites
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Ga<S: Solution> {
+    engine: Engine<S>,
+    params: GaParams,
+    /// Sorted from best to worst
+    population: Vec<Individual<S>>,
+}
+
+impl<S: Solution> Ga<S> {
+    /// Use `params` instead of the default [`GaParams`].
+    pub fn with_params(mut self, params: GaParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn params(&self) -> &GaParams {
+        &self.params
+    }
+
+    /// Sort the population from best to worst and record it.
+    fn sort_and_record(&mut self) {
+        self.population.sort_by(compare);
+
+        let fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &fronts);
+    }
+
+    /// Parents drawn by tournaments; the population is sorted, so the lowest index wins
+    fn tournament_selection(&mut self, count: usize) -> Vec<S> {
+        let mut mating_pool = Vec::with_capacity(count);
+
+        while mating_pool.len() < count {
+            let mut winner = self.engine.rng.gen_range(0..self.population.len());
+
+            for _ in 1..self.engine.config.tournament_size() {
+                winner = winner.min(self.engine.rng.gen_range(0..self.population.len()));
+            }
+
+            mating_pool.push(self.population[winner].solution.clone());
+        }
+
+        mating_pool
+    }
+}
+
+/// Feasible before infeasible, then by objective value
+fn compare<S>(a: &Individual<S>, b: &Individual<S>) -> Ordering {
+    b.feasible.cmp(&a.feasible).then_with(|| a.objectives[0].total_cmp(&b.objectives[0]))
+}
+
+impl<S: Solution> WithEngine<S> for Ga<S> {
+    fn from_engine(engine: Engine<S>) -> Self {
+        Self {
+            population: Vec::with_capacity(engine.config.population_size()),
+            engine,
+            params: GaParams::default(),
+        }
+    }
+
+    fn engine_mut(&mut self) -> &mut Engine<S> {
+        &mut self.engine
+    }
+}
+
+impl<S: Solution> Algorithm<S> for Ga<S> {
+    fn initialize(&mut self) -> Result<(), RunError> {
+        self.engine.start();
+
+        self.population = self.engine.initial_population(self.engine.config.population_size())?;
+
+        if self.engine.objective_count != 1 {
+            return Err(RunError::NotSingleObjective(self.engine.objective_count));
+        }
+
+        self.sort_and_record();
+        Ok(())
+    }
+
+    /// Keep the elites and replace everyone else by offspring.
+    fn step(&mut self) -> Result<(), RunError> {
+        let elites = self.params.elites.min(self.population.len());
+        let count = self.engine.config.population_size() - elites;
+
+        let mating_pool = self.tournament_selection(count);
+        let offspring = self.engine.offspring(&mating_pool, mating_pool.len(), 1)?;
+
+        self.population.truncate(elites);
+        self.population.extend(offspring);
+
+        self.engine.generation += 1;
+        self.sort_and_record();
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.engine.finished()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.engine.add_observer(observer);
+    }
+
+    fn population(&self) -> &[Individual<S>] {
+        &self.population
+    }
+
+    fn archive(&self) -> Option<&Archive<S>> {
+        self.engine.archive.as_ref()
+    }
+
+    fn best(&self) -> Option<&Individual<S>> {
+        self.engine.best.as_ref()
+    }
+
+    /// The archive if there is one, otherwise the best solution ever evaluated
+    fn result(&self) -> Vec<&Individual<S>> {
+        match (self.archive(), self.best()) {
+            (Some(archive), _) => archive.members().iter().collect(),
+            (None, Some(best)) => vec![best],
+            (None, None) => pareto_front(self.population()),
+        }
+    }
+
+    fn config(&self) -> &Config {
+        &self.engine.config
+    }
+
+    fn statistics(&self) -> &RunStatistics {
+        &self.engine.statistics
+    }
+
+    fn history(&self) -> &RunHistory {
+        &self.engine.history
+    }
+}
@@ -0,0 +1,81 @@
+//! Ready-made decision-variable encodings with variation operators, so that
+//! problems only have to define their objectives and constraints.
+
+pub mod bits;
+pub mod mixed;
+pub mod permutation;
+pub mod variable;
+
+use rand::Rng;
+
+/// Distribution index of simulated binary crossover; larger keeps children closer to their parents
+pub const SBX_ETA: f64 = 15.0;
+
+/// Distribution index of polynomial mutation; larger makes smaller steps
+pub const MUTATION_ETA: f64 = 20.0;
+
+/// Check that there is one of `values` for every pair of `bounds`, each
+/// within its inclusive bounds.
+pub fn check_reals(values: &[f64], bounds: &[(f64, f64)]) -> Result<(), String> {
+    if values.len() != bounds.len() {
+        return Err(format!("expected {} variables, got {}", bounds.len(), values.len()));
+    }
+
+    match values.iter().zip(bounds).enumerate().find(|(_, (x, (low, high)))| !(low..=high).contains(x)) {
+        Some((i, (x, (low, high)))) => Err(format!("variable {} = {} is outside [{}, {}]", i + 1, x, low, high)),
+        None => Ok(()),
+    }
+}
+
+/// One child value of bounded simulated binary crossover (Deb and Agrawal, 1995)
+/// between `a` and `b`, both within `[low, high]`.
+pub fn sbx<R: Rng + ?Sized>(a: f64, b: f64, low: f64, high: f64, rng: &mut R) -> f64 {
+    let (y1, y2) = if a < b { (a, b) } else { (b, a) };
+
+    if y2 - y1 < 1e-14 {
+        return a;
+    }
+
+    let exponent = 1.0 / (SBX_ETA + 1.0);
+    let u = rng.gen::<f64>();
+
+    // Spread factor, with the distribution cut off where it would leave the bounds
+    let spread = |beta: f64| {
+        let alpha = 2.0 - beta.powf(-(SBX_ETA + 1.0));
+        if u <= 1.0 / alpha {
+            (u * alpha).powf(exponent)
+        } else {
+            (1.0 / (2.0 - u * alpha)).powf(exponent)
+        }
+    };
+
+    let child = if rng.gen_bool(0.5) {
+        0.5 * ((y1 + y2) - spread(1.0 + 2.0 * (y1 - low) / (y2 - y1)) * (y2 - y1))
+    } else {
+        0.5 * ((y1 + y2) + spread(1.0 + 2.0 * (high - y2) / (y2 - y1)) * (y2 - y1))
+    };
+
+    child.clamp(low, high)
+}
+
+/// `value` moved by bounded polynomial mutation (Deb and Goyal, 1996) within `[low, high]`.
+pub fn polynomial_mutation<R: Rng + ?Sized>(value: f64, low: f64, high: f64, rng: &mut R) -> f64 {
+    let range = high - low;
+
+    if range <= 0.0 {
+        return value;
+    }
+
+    let exponent = 1.0 / (MUTATION_ETA + 1.0);
+    let u = rng.gen::<f64>();
+
+    let shift = if u < 0.5 {
+        let room = 1.0 - (value - low) / range;
+        (2.0 * u + (1.0 - 2.0 * u) * room.powf(MUTATION_ETA + 1.0)).powf(exponent) - 1.0
+    } else {
+        let room = 1.0 - (high - value) / range;
+        1.0 - (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * room.powf(MUTATION_ETA + 1.0)).powf(exponent)
+    };
+
+    (value + shift * range).clamp(low, high)
+}
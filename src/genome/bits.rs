@@ -0,0 +1,139 @@
+//! Fixed-length bitstrings, for selection and subset problems.
+
+use std::fmt;
+
+use rand::prelude::*;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// How two bitstrings are recombined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BitCrossover {
+    /// Head of one parent, tail of the other
+    OnePoint,
+    /// Middle section of one parent, the rest from the other
+    TwoPoint,
+    /// Every bit from either parent with equal probability
+    #[default]
+    Uniform,
+}
+
+/// A bitstring, written as a string of `0` and `1` characters.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bits(pub Vec<bool>);
+
+impl Bits {
+    /// `len` bits, each set with probability one half
+    pub fn random<R: Rng + ?Sized>(len: usize, rng: &mut R) -> Self {
+        Self((0..len).map(|_| rng.gen()).collect())
+    }
+
+    /// One bit per coordinate of `point`, set from one half up
+    pub fn from_unit(point: &[f64]) -> Self {
+        Self(point.iter().map(|&u| u >= 0.5).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.0.iter().filter(|&&bit| bit).count()
+    }
+
+    /// Positions of the set bits
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().filter(|(_, &bit)| bit).map(|(i, _)| i)
+    }
+
+    /// Flip each bit with probability `1 / len`, and at least one.
+    pub fn flip<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let len = self.len();
+
+        if len == 0 {
+            return;
+        }
+
+        let mut flipped = false;
+        for bit in &mut self.0 {
+            if rng.gen_bool(1.0 / len as f64) {
+                *bit = !*bit;
+                flipped = true;
+            }
+        }
+
+        if !flipped {
+            let i = rng.gen_range(0..len);
+            self.0[i] = !self.0[i];
+        }
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, other: &Self, kind: BitCrossover, rng: &mut R) -> Self {
+        match kind {
+            BitCrossover::OnePoint => self.one_point(other, rng),
+            BitCrossover::TwoPoint => self.two_point(other, rng),
+            BitCrossover::Uniform => self.uniform(other, rng),
+        }
+    }
+
+    pub fn one_point<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let cut = rng.gen_range(0..=self.len());
+        Self(self.0[..cut].iter().chain(&other.0[cut..]).copied().collect())
+    }
+
+    pub fn two_point<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let a = rng.gen_range(0..=self.len());
+        let b = rng.gen_range(0..=self.len());
+        let (start, end) = (a.min(b), a.max(b));
+
+        let mut child = self.clone();
+        child.0[start..end].copy_from_slice(&other.0[start..end]);
+        child
+    }
+
+    pub fn uniform<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self(
+            self.0
+                .iter()
+                .zip(&other.0)
+                .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &bit in &self.0 {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Bits {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bits {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+
+        text.chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                other => Err(de::Error::custom(format!("invalid bit {:?}", other))),
+            })
+            .collect::<Result<_, _>>()
+            .map(Bits)
+    }
+}
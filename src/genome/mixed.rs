@@ -0,0 +1,308 @@
+//! Real, integer, categorical and boolean variables side by side, each varied
+//! by an operator suited to its type.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{polynomial_mutation, sbx};
+
+/// Values of a mixed-variable genome, grouped by type. The meaning and bounds
+/// of each position come from the matching [`MixedSpace`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Mixed {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reals: Vec<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub integers: Vec<i64>,
+    /// Index of the chosen option of each categorical variable
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub booleans: Vec<bool>,
+}
+
+/// The variables of a mixed-variable problem and their bounds.
+///
+/// Real variables are recombined by simulated binary crossover and mutated
+/// polynomially, integers the same way with rounding, and categorical and
+/// boolean variables are inherited from either parent and mutated by picking
+/// another option.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MixedSpace {
+    /// Inclusive bounds of every real variable
+    pub reals: Vec<(f64, f64)>,
+    /// Inclusive bounds of every integer variable
+    pub integers: Vec<(i64, i64)>,
+    /// Number of options of every categorical variable
+    pub categories: Vec<usize>,
+    /// Number of boolean variables
+    pub booleans: usize,
+}
+
+impl MixedSpace {
+    /// Total number of variables
+    pub fn len(&self) -> usize {
+        self.reals.len() + self.integers.len() + self.categories.len() + self.booleans
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check that every range is non-empty and every categorical variable has an option
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, &(low, high)) in self.reals.iter().enumerate() {
+            if !(low.is_finite() && high.is_finite() && low <= high) {
+                return Err(format!("real variable {} has invalid bounds [{}, {}]", i, low, high));
+            }
+        }
+
+        for (i, &(low, high)) in self.integers.iter().enumerate() {
+            if low > high {
+                return Err(format!("integer variable {} has invalid bounds [{}, {}]", i, low, high));
+            }
+        }
+
+        if let Some(i) = self.categories.iter().position(|&options| options == 0) {
+            return Err(format!("categorical variable {} has no options", i));
+        }
+
+        Ok(())
+    }
+
+    /// Whether `genome` has as many variables of each type as the space
+    pub fn fits(&self, genome: &Mixed) -> bool {
+        genome.reals.len() == self.reals.len()
+            && genome.integers.len() == self.integers.len()
+            && genome.categories.len() == self.categories.len()
+            && genome.booleans.len() == self.booleans
+    }
+
+    /// Whether `genome` has the right shape and every value is within bounds
+    pub fn contains(&self, genome: &Mixed) -> bool {
+        self.fits(genome)
+            && genome.reals.iter().zip(&self.reals).all(|(x, &(low, high))| (low..=high).contains(x))
+            && genome.integers.iter().zip(&self.integers).all(|(x, &(low, high))| (low..=high).contains(x))
+            && genome.categories.iter().zip(&self.categories).all(|(&x, &options)| x < options)
+    }
+
+    /// Like [`MixedSpace::contains`], saying what is wrong with `genome` if it is not in the space
+    pub fn check(&self, genome: &Mixed) -> Result<(), String> {
+        if !self.fits(genome) {
+            return Err(format!(
+                "expected {} real, {} integer, {} categorical and {} boolean variables, got {}, {}, {} and {}",
+                self.reals.len(),
+                self.integers.len(),
+                self.categories.len(),
+                self.booleans,
+                genome.reals.len(),
+                genome.integers.len(),
+                genome.categories.len(),
+                genome.booleans.len()
+            ));
+        }
+
+        for (i, (x, &(low, high))) in genome.reals.iter().zip(&self.reals).enumerate() {
+            if !(low..=high).contains(x) {
+                return Err(format!("real variable {} = {} is outside [{}, {}]", i + 1, x, low, high));
+            }
+        }
+        for (i, (x, &(low, high))) in genome.integers.iter().zip(&self.integers).enumerate() {
+            if !(low..=high).contains(x) {
+                return Err(format!("integer variable {} = {} is outside [{}, {}]", i + 1, x, low, high));
+            }
+        }
+        for (i, (&x, &options)) in genome.categories.iter().zip(&self.categories).enumerate() {
+            if x >= options {
+                return Err(format!("categorical variable {} = {} is not one of its {} options", i + 1, x, options));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A genome with every variable drawn uniformly within its bounds
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Mixed {
+        Mixed {
+            reals: self.reals.iter().map(|&(low, high)| rng.gen_range(low..=high)).collect(),
+            integers: self.integers.iter().map(|&(low, high)| rng.gen_range(low..=high)).collect(),
+            categories: self.categories.iter().map(|&options| rng.gen_range(0..options)).collect(),
+            booleans: (0..self.booleans).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    /// The genome at `point` of the unit hypercube, with one coordinate per
+    /// variable in the order reals, integers, categories, booleans. Integers and
+    /// categories take equally wide slices of `[0, 1]` per value.
+    pub fn genome_at(&self, point: &[f64]) -> Mixed {
+        let (reals, rest) = point.split_at(self.reals.len());
+        let (integers, rest) = rest.split_at(self.integers.len());
+        let (categories, booleans) = rest.split_at(self.categories.len());
+
+        Mixed {
+            reals: reals.iter().zip(&self.reals).map(|(u, &(low, high))| low + u * (high - low)).collect(),
+            integers: integers
+                .iter()
+                .zip(&self.integers)
+                .map(|(u, &(low, high))| (low + (u * (high - low + 1) as f64).floor() as i64).min(high))
+                .collect(),
+            categories: categories
+                .iter()
+                .zip(&self.categories)
+                .map(|(u, &options)| ((u * options as f64) as usize).min(options - 1))
+                .collect(),
+            booleans: booleans.iter().map(|&u| u >= 0.5).collect(),
+        }
+    }
+
+    /// A child taking half of its variables, on average, from each parent, and
+    /// blending real and integer ones.
+    ///
+    /// Panics if either parent does not [fit](MixedSpace::fits) the space.
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &Mixed, b: &Mixed, rng: &mut R) -> Mixed {
+        assert!(self.fits(a) && self.fits(b), "parents do not match the layout of the space");
+
+        let reals = a
+            .reals
+            .iter()
+            .zip(&b.reals)
+            .zip(&self.reals)
+            .map(|((&x, &y), &(low, high))| if rng.gen_bool(0.5) { sbx(x, y, low, high, rng) } else { x })
+            .collect();
+
+        let integers = a
+            .integers
+            .iter()
+            .zip(&b.integers)
+            .zip(&self.integers)
+            .map(|((&x, &y), &(low, high))| {
+                if rng.gen_bool(0.5) {
+                    let child = sbx(x as f64, y as f64, low as f64, high as f64, rng);
+                    (child.round() as i64).clamp(low, high)
+                } else {
+                    x
+                }
+            })
+            .collect();
+
+        fn pick<T, R: Rng + ?Sized>(x: T, y: T, rng: &mut R) -> T {
+            if rng.gen_bool(0.5) {
+                x
+            } else {
+                y
+            }
+        }
+
+        let categories = a.categories.iter().zip(&b.categories).map(|(&x, &y)| pick(x, y, rng)).collect();
+        let booleans = a.booleans.iter().zip(&b.booleans).map(|(&x, &y)| pick(x, y, rng)).collect();
+
+        Mixed { reals, integers, categories, booleans }
+    }
+
+    /// Mutate each variable with probability `1 / len`, and at least one.
+    ///
+    /// Panics if `genome` does not [fit](MixedSpace::fits) the space.
+    pub fn mutate<R: Rng + ?Sized>(&self, genome: &mut Mixed, rng: &mut R) {
+        assert!(self.fits(genome), "genome does not match the layout of the space");
+
+        let count = self.len();
+
+        if count == 0 {
+            return;
+        }
+
+        let probability = 1.0 / count as f64;
+        let mut chosen = (0..count).filter(|_| rng.gen_bool(probability)).collect::<Vec<_>>();
+        if chosen.is_empty() {
+            chosen.push(rng.gen_range(0..count));
+        }
+
+        for mut i in chosen {
+            if let Some(&(low, high)) = self.reals.get(i) {
+                genome.reals[i] = polynomial_mutation(genome.reals[i], low, high, rng);
+                continue;
+            }
+            i -= self.reals.len();
+
+            if let Some(&(low, high)) = self.integers.get(i) {
+                let value = polynomial_mutation(genome.integers[i] as f64, low as f64, high as f64, rng);
+                let mut value = (value.round() as i64).clamp(low, high);
+
+                // Small steps round back to where they started
+                if value == genome.integers[i] && low < high {
+                    value = if value == high || (value > low && rng.gen_bool(0.5)) { value - 1 } else { value + 1 };
+                }
+
+                genome.integers[i] = value;
+                continue;
+            }
+            i -= self.integers.len();
+
+            if let Some(&options) = self.categories.get(i) {
+                if options > 1 {
+                    // Any option but the current one
+                    let other = rng.gen_range(0..options - 1);
+                    genome.categories[i] = if other >= genome.categories[i] { other + 1 } else { other };
+                }
+                continue;
+            }
+            i -= self.categories.len();
+
+            genome.booleans[i] = !genome.booleans[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    fn space() -> MixedSpace {
+        MixedSpace {
+            reals: vec![(0.0, 1.0); 2],
+            integers: vec![(-3, 3)],
+            categories: vec![4],
+            booleans: 1,
+        }
+    }
+
+    #[test]
+    fn variation_keeps_genomes_in_the_space() {
+        let space = space();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let a = space.random(&mut rng);
+            let b = space.random(&mut rng);
+            let mut child = space.crossover(&a, &b, &mut rng);
+            space.mutate(&mut child, &mut rng);
+            assert!(space.contains(&child));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "parents do not match")]
+    fn crossover_rejects_a_shorter_parent() {
+        let space = space();
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = space.random(&mut rng);
+        let mut b = space.random(&mut rng);
+        b.reals.pop();
+
+        space.crossover(&a, &b, &mut rng);
+    }
+
+    #[test]
+    #[should_panic(expected = "genome does not match")]
+    fn mutate_rejects_a_shorter_genome() {
+        let space = space();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut genome = space.random(&mut rng);
+        genome.booleans.clear();
+
+        space.mutate(&mut genome, &mut rng);
+    }
+}
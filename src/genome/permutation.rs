@@ -0,0 +1,220 @@
+//! Orderings of `0..n`, for sequencing, scheduling and routing problems.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How two permutations are recombined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermutationCrossover {
+    /// Order crossover (OX): a slice of one parent, the rest in the other parent's order
+    #[default]
+    Order,
+    /// Partially mapped crossover (PMX): a slice of one parent, the rest from
+    /// the other parent in place where possible
+    PartiallyMapped,
+    /// Cycle crossover (CX): every element at a position it has in one of the parents
+    Cycle,
+}
+
+/// How a permutation is perturbed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermutationMutation {
+    /// Exchange two elements
+    #[default]
+    Swap,
+    /// Move one element to another position
+    Insert,
+    /// Reverse a slice
+    Inversion,
+}
+
+/// An ordering of the elements `0..len`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Permutation(pub Vec<usize>);
+
+impl Permutation {
+    /// A uniformly random ordering of `0..len`
+    pub fn random<R: Rng + ?Sized>(len: usize, rng: &mut R) -> Self {
+        let mut order = (0..len).collect::<Vec<_>>();
+        order.shuffle(rng);
+        Self(order)
+    }
+
+    /// The positions of `keys` in increasing order of their values, so that
+    /// any point of the unit hypercube encodes an ordering (random keys)
+    pub fn from_keys(keys: &[f64]) -> Self {
+        let mut order = (0..keys.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+        Self(order)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether every element of `0..len` appears exactly once
+    pub fn is_valid(&self) -> bool {
+        let mut seen = vec![false; self.len()];
+        self.0.iter().all(|&x| x < seen.len() && !std::mem::replace(&mut seen[x], true))
+    }
+
+    /// Check that this is an ordering of `0..len`
+    pub fn check(&self, len: usize) -> Result<(), String> {
+        if self.len() != len {
+            return Err(format!("expected an ordering of {} elements, got {}", len, self.len()));
+        }
+        if !self.is_valid() {
+            return Err(format!("not an ordering of 0..{}", len));
+        }
+
+        Ok(())
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, other: &Self, kind: PermutationCrossover, rng: &mut R) -> Self {
+        match kind {
+            PermutationCrossover::Order => self.order_crossover(other, rng),
+            PermutationCrossover::PartiallyMapped => self.partially_mapped_crossover(other, rng),
+            PermutationCrossover::Cycle => self.cycle_crossover(other, rng),
+        }
+    }
+
+    pub fn mutate<R: Rng + ?Sized>(&mut self, kind: PermutationMutation, rng: &mut R) {
+        match kind {
+            PermutationMutation::Swap => self.swap(rng),
+            PermutationMutation::Insert => self.insert(rng),
+            PermutationMutation::Inversion => self.invert(rng),
+        }
+    }
+
+    /// Keep a random slice of `self` and fill the other positions, starting
+    /// after the slice and wrapping around, with the missing elements in the
+    /// order they appear in `other` from the same point.
+    pub fn order_crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let n = self.len();
+        let (start, end) = slice(n, rng);
+
+        let mut taken = vec![false; n];
+        for &x in &self.0[start..end] {
+            taken[x] = true;
+        }
+
+        let mut child = self.0.clone();
+        let mut fill = (end..n).chain(0..start);
+        for offset in 0..n {
+            let x = other.0[(end + offset) % n];
+            if !taken[x] {
+                // There are exactly as many free positions as missing elements
+                child[fill.next().unwrap()] = x;
+            }
+        }
+
+        Self(child)
+    }
+
+    /// Keep a random slice of `self` and take the other positions from
+    /// `other`, following the mapping the slice defines wherever the element
+    /// from `other` is already in the slice.
+    pub fn partially_mapped_crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let n = self.len();
+        let (start, end) = slice(n, rng);
+
+        // Position of every element in `self`, to follow the mapping
+        let mut position = vec![0; n];
+        for (i, &x) in self.0.iter().enumerate() {
+            position[x] = i;
+        }
+
+        let mut child = self.0.clone();
+        for i in (0..start).chain(end..n) {
+            let mut x = other.0[i];
+            while (start..end).contains(&position[x]) {
+                x = other.0[position[x]];
+            }
+            child[i] = x;
+        }
+
+        Self(child)
+    }
+
+    /// Split the positions into the cycles the two parents form, and take
+    /// alternate cycles from each parent.
+    pub fn cycle_crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let n = self.len();
+
+        let mut position = vec![0; n];
+        for (i, &x) in self.0.iter().enumerate() {
+            position[x] = i;
+        }
+
+        let mut child = self.0.clone();
+        let mut visited = vec![false; n];
+        // Random first parent, so that children of a pair are not all alike
+        let mut from_other = rng.gen_bool(0.5);
+
+        for first in 0..n {
+            if visited[first] {
+                continue;
+            }
+
+            let mut i = first;
+            while !visited[i] {
+                visited[i] = true;
+                if from_other {
+                    child[i] = other.0[i];
+                }
+                i = position[other.0[i]];
+            }
+
+            from_other = !from_other;
+        }
+
+        Self(child)
+    }
+
+    /// Exchange two distinct elements
+    pub fn swap<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if let Some((i, j)) = two_positions(self.len(), rng) {
+            self.0.swap(i, j);
+        }
+    }
+
+    /// Move an element to a different position, shifting those in between
+    pub fn insert<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if let Some((from, to)) = two_positions(self.len(), rng) {
+            let x = self.0.remove(from);
+            self.0.insert(to, x);
+        }
+    }
+
+    /// Reverse the order of a slice of at least two elements
+    pub fn invert<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if let Some((i, j)) = two_positions(self.len(), rng) {
+            self.0[i.min(j)..=i.max(j)].reverse();
+        }
+    }
+}
+
+/// Bounds of a random slice of `0..n`, possibly empty
+fn slice<R: Rng + ?Sized>(n: usize, rng: &mut R) -> (usize, usize) {
+    let a = rng.gen_range(0..=n);
+    let b = rng.gen_range(0..=n);
+    (a.min(b), a.max(b))
+}
+
+/// Two distinct positions in `0..n`, if there are two
+fn two_positions<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Option<(usize, usize)> {
+    if n < 2 {
+        return None;
+    }
+
+    let i = rng.gen_range(0..n);
+    let j = rng.gen_range(0..n - 1);
+    Some((i, if j >= i { j + 1 } else { j }))
+}
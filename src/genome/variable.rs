@@ -0,0 +1,159 @@
+//! Lists of genes whose length is itself a decision, for problems with a
+//! variable number of components.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::mixed::{Mixed, MixedSpace};
+
+/// How two lists, possibly of different lengths, are recombined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableCrossover {
+    /// Head of one parent joined to the tail of the other, cut at independent
+    /// points so that the child can be longer or shorter than both
+    #[default]
+    CutAndSplice,
+    /// Genes at the same position recombined with each other, the child as
+    /// long as either parent and the surplus genes copied from the longer one
+    Aligned,
+}
+
+/// A list of genes, each described by the same [`MixedSpace`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Variable(pub Vec<Mixed>);
+
+impl Variable {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Bounds on the length of a [`Variable`] and the space of each of its genes.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VariableSpace {
+    pub gene: MixedSpace,
+    /// Fewest genes, inclusive
+    pub min_len: usize,
+    /// Most genes, inclusive
+    pub max_len: usize,
+    pub crossover: VariableCrossover,
+}
+
+impl VariableSpace {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_len > self.max_len {
+            return Err(format!("invalid length bounds [{}, {}]", self.min_len, self.max_len));
+        }
+
+        self.gene.validate()
+    }
+
+    /// Whether `genome` has an allowed length and every gene is within the gene space
+    pub fn contains(&self, genome: &Variable) -> bool {
+        (self.min_len..=self.max_len).contains(&genome.len()) && genome.0.iter().all(|gene| self.gene.contains(gene))
+    }
+
+    /// Like [`VariableSpace::contains`], saying what is wrong with `genome` if it is not in the space
+    pub fn check(&self, genome: &Variable) -> Result<(), String> {
+        if !(self.min_len..=self.max_len).contains(&genome.len()) {
+            return Err(format!("expected {} to {} genes, got {}", self.min_len, self.max_len, genome.len()));
+        }
+
+        for (i, gene) in genome.0.iter().enumerate() {
+            self.gene.check(gene).map_err(|e| format!("gene {}: {}", i + 1, e))?;
+        }
+
+        Ok(())
+    }
+
+    /// A genome of uniformly random length with every gene drawn uniformly
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Variable {
+        let len = rng.gen_range(self.min_len..=self.max_len);
+        Variable((0..len).map(|_| self.gene.random(rng)).collect())
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &Variable, b: &Variable, rng: &mut R) -> Variable {
+        match self.crossover {
+            VariableCrossover::CutAndSplice => self.cut_and_splice(a, b, rng),
+            VariableCrossover::Aligned => self.aligned(a, b, rng),
+        }
+    }
+
+    /// `a` up to one cut followed by `b` from another, with the cuts chosen so
+    /// that the child length stays within bounds.
+    pub fn cut_and_splice<R: Rng + ?Sized>(&self, a: &Variable, b: &Variable, rng: &mut R) -> Variable {
+        let (la, lb) = (a.len(), b.len());
+
+        // The child has `ca + lb - cb` genes, for any `cb` in `0..=lb`
+        let ca_low = self.min_len.saturating_sub(lb);
+        let ca_high = la.min(self.max_len);
+        if ca_low > ca_high {
+            // Parents too far out of bounds to splice
+            return a.clone();
+        }
+
+        let ca = rng.gen_range(ca_low..=ca_high);
+        let cb_low = (ca + lb).saturating_sub(self.max_len);
+        let cb_high = (ca + lb).saturating_sub(self.min_len).min(lb);
+        let cb = rng.gen_range(cb_low..=cb_high);
+        Variable(a.0[..ca].iter().chain(&b.0[cb..]).cloned().collect())
+    }
+
+    /// Gene-wise crossover over the positions both parents have, with the
+    /// child as long as one of them chosen at random.
+    pub fn aligned<R: Rng + ?Sized>(&self, a: &Variable, b: &Variable, rng: &mut R) -> Variable {
+        let len = if rng.gen_bool(0.5) { a.len() } else { b.len() };
+
+        Variable(
+            (0..len)
+                .map(|i| match (a.0.get(i), b.0.get(i)) {
+                    (Some(x), Some(y)) => self.gene.crossover(x, y, rng),
+                    (Some(x), None) | (None, Some(x)) => x.clone(),
+                    (None, None) => unreachable!("child is as long as one of its parents"),
+                })
+                .collect(),
+        )
+    }
+
+    /// Insert a random gene, remove one or mutate one, each equally likely
+    /// among those the length bounds allow.
+    pub fn mutate<R: Rng + ?Sized>(&self, genome: &mut Variable, rng: &mut R) {
+        #[derive(Clone, Copy)]
+        enum Move {
+            Grow,
+            Shrink,
+            Change,
+        }
+
+        let len = genome.len();
+        let mut moves = Vec::with_capacity(3);
+        if len < self.max_len {
+            moves.push(Move::Grow);
+        }
+        if len > self.min_len && len > 0 {
+            moves.push(Move::Shrink);
+        }
+        if len > 0 {
+            moves.push(Move::Change);
+        }
+
+        match moves.choose(rng) {
+            Some(Move::Grow) => genome.0.insert(rng.gen_range(0..=len), self.gene.random(rng)),
+            Some(Move::Shrink) => {
+                genome.0.remove(rng.gen_range(0..len));
+            }
+            Some(Move::Change) => {
+                let i = rng.gen_range(0..len);
+                self.gene.mutate(&mut genome.0[i], rng);
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+
+/// Minimum, mean and maximum of one objective over a population.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveSummary {
+    pub min: f64,
+    pub mean: f64,
+    pub max: f64,
+}
+
+/// The state of the population at the end of one generation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerationRecord {
+    /// Generations completed, so the initial population is generation 0
+    pub generation: usize,
+    pub evaluations: usize,
+    pub elapsed: Duration,
+    /// Number of non-dominated fronts the population sorts into
+    pub fronts: usize,
+    pub first_front_size: usize,
+    pub feasible_ratio: f64,
+    pub objectives: Vec<ObjectiveSummary>,
+    /// Best feasible objective value found so far, for single-objective problems
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best: Option<f64>,
+}
+
+impl GenerationRecord {
+    /// Summarize `population`, which sorts into `fronts` fronts, the first of size `first_front_size`.
+    pub fn new<S>(
+        generation: usize,
+        evaluations: usize,
+        elapsed: Duration,
+        population: &[Individual<S>],
+        fronts: usize,
+        first_front_size: usize,
+    ) -> Self {
+        let objective_count = population.first().map_or(0, |i| i.objectives.len());
+
+        let objectives = (0..objective_count)
+            .map(|m| {
+                let values = population.iter().map(|individual| individual.objectives[m]);
+
+                ObjectiveSummary {
+                    min: values.clone().fold(f64::INFINITY, f64::min),
+                    mean: values.clone().sum::<f64>() / population.len() as f64,
+                    max: values.fold(f64::NEG_INFINITY, f64::max),
+                }
+            })
+            .collect();
+
+        let feasible = population.iter().filter(|individual| individual.feasible).count();
+
+        Self {
+            generation,
+            evaluations,
+            elapsed,
+            fronts,
+            first_front_size,
+            feasible_ratio: feasible as f64 / population.len().max(1) as f64,
+            objectives,
+            best: None,
+        }
+    }
+}
+
+/// Called by the engine at the end of every generation, starting with the initial population.
+pub trait Observer<S> {
+    fn observe(&mut self, record: &GenerationRecord, population: &[Individual<S>]);
+}
+
+impl<S, F: FnMut(&GenerationRecord, &[Individual<S>])> Observer<S> for F {
+    fn observe(&mut self, record: &GenerationRecord, population: &[Individual<S>]) {
+        self(record, population)
+    }
+}
+
+/// Per-generation statistics of a run, for following its convergence.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunHistory {
+    pub generations: Vec<GenerationRecord>,
+}
+
+impl RunHistory {
+    pub fn push(&mut self, record: GenerationRecord) {
+        self.generations.push(record);
+    }
+
+    pub fn last(&self) -> Option<&GenerationRecord> {
+        self.generations.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+
+    /// Write one row per generation, with `f<m>_min`, `f<m>_mean` and `f<m>_max`
+    /// columns for every objective, and a `best` column with the convergence
+    /// curve of single-objective runs.
+    pub fn write_csv<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        let objective_count = self.generations.first().map_or(0, |g| g.objectives.len());
+        let single_objective = objective_count == 1;
+
+        let mut header = ["generation", "evaluations", "elapsed_seconds", "fronts", "first_front_size", "feasible_ratio"]
+            .map(String::from)
+            .to_vec();
+        for m in 1..=objective_count {
+            header.extend(["min", "mean", "max"].map(|statistic| format!("f{}_{}", m, statistic)));
+        }
+        if single_objective {
+            header.push("best".to_string());
+        }
+        writeln!(writer, "{}", header.join(","))?;
+
+        for record in &self.generations {
+            write!(
+                writer,
+                "{},{},{},{},{},{}",
+                record.generation,
+                record.evaluations,
+                record.elapsed.as_secs_f64(),
+                record.fronts,
+                record.first_front_size,
+                record.feasible_ratio
+            )?;
+
+            for summary in &record.objectives {
+                write!(writer, ",{},{},{}", summary.min, summary.mean, summary.max)?;
+            }
+
+            if single_objective {
+                // Empty until a feasible solution has been found
+                match record.best {
+                    Some(best) => write!(writer, ",{}", best)?,
+                    None => write!(writer, ",")?,
+                }
+            }
+
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::dominance::dominates;
+
+/// A solution together with its cached evaluation.
+#[derive(Clone, Debug)]
+pub struct Individual<S> {
+    pub solution: S,
+    pub objectives: Vec<f64>,
+    pub feasible: bool,
+}
+
+impl<S> Individual<S> {
+    pub fn dominates(&self, other: &Self) -> bool {
+        dominates(&self.objectives, &other.objectives)
+    }
+}
+
+/// Feasible members of `population` not dominated by any other feasible member
+pub fn pareto_front<S>(population: &[Individual<S>]) -> Vec<&Individual<S>> {
+    let feasible = population.iter().filter(|i| i.feasible).collect::<Vec<_>>();
+
+    feasible
+        .iter()
+        .filter(|candidate| !feasible.iter().any(|other| other.dominates(candidate)))
+        .copied()
+        .collect()
+}
@@ -0,0 +1,308 @@
+//! How the initial population is drawn: by the problem itself, or from points
+//! of the unit hypercube spread more evenly than independent uniform draws,
+//! which the problem then scales to its bounds.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Strategy for drawing the initial population.
+///
+/// Every strategy but [`Initialization::Random`] needs a problem that maps
+/// points of the unit hypercube to solutions, see
+/// [`Problem::solution_at`](crate::solution::Problem::solution_at).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Initialization {
+    /// Independent draws from the problem's own random solutions
+    #[default]
+    Random,
+    /// Every variable takes a value from each of `n` equally wide strata exactly once
+    LatinHypercube,
+    /// The Halton sequence, with a random shift modulo 1
+    Halton,
+    /// The Sobol sequence, with a random digital shift
+    Sobol,
+    /// Uniform points together with their opposites, keeping the better half
+    Opposition,
+}
+
+impl Initialization {
+    pub const ALL: [Initialization; 5] = [
+        Initialization::Random,
+        Initialization::LatinHypercube,
+        Initialization::Halton,
+        Initialization::Sobol,
+        Initialization::Opposition,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Initialization::Random => "random",
+            Initialization::LatinHypercube => "latin_hypercube",
+            Initialization::Halton => "halton",
+            Initialization::Sobol => "sobol",
+            Initialization::Opposition => "opposition",
+        }
+    }
+}
+
+/// `count` points in `dimension` coordinates such that, in every coordinate,
+/// each of the `count` equally wide strata of `[0, 1)` holds exactly one point.
+pub fn latin_hypercube<R: Rng + ?Sized>(count: usize, dimension: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    let mut points = vec![Vec::with_capacity(dimension); count];
+    let mut strata = (0..count).collect::<Vec<_>>();
+
+    for _ in 0..dimension {
+        strata.shuffle(rng);
+
+        for (point, &stratum) in points.iter_mut().zip(&strata) {
+            point.push((stratum as f64 + rng.gen::<f64>()) / count as f64);
+        }
+    }
+
+    points
+}
+
+/// The Halton sequence, using the first `dimension` primes as bases.
+#[derive(Clone, Debug)]
+pub struct Halton {
+    bases: Vec<u64>,
+}
+
+impl Halton {
+    pub fn new(dimension: usize) -> Self {
+        let mut bases: Vec<u64> = Vec::with_capacity(dimension);
+        let mut candidate = 2;
+
+        while bases.len() < dimension {
+            if bases.iter().take_while(|&&p| p * p <= candidate).all(|&p| candidate % p != 0) {
+                bases.push(candidate);
+            }
+            candidate += 1;
+        }
+
+        Self { bases }
+    }
+
+    pub fn point(&self, index: u64) -> Vec<f64> {
+        self.bases.iter().map(|&base| radical_inverse(index, base)).collect()
+    }
+}
+
+/// The digits of `index` in `base` mirrored around the radix point
+fn radical_inverse(mut index: u64, base: u64) -> f64 {
+    let mut result = 0.0;
+    let mut scale = 1.0 / base as f64;
+
+    while index > 0 {
+        result += (index % base) as f64 * scale;
+        index /= base;
+        scale /= base as f64;
+    }
+
+    result
+}
+
+/// Bits of precision of every coordinate of a [`Sobol`] point
+const SOBOL_BITS: usize = 32;
+
+/// Number of distinct points of a [`Sobol`] sequence
+pub const SOBOL_PERIOD: u64 = 1 << SOBOL_BITS;
+
+/// The Sobol sequence, with one primitive polynomial over GF(2) per coordinate
+/// after the first, in order of degree, and all initial direction numbers 1.
+#[derive(Clone, Debug)]
+pub struct Sobol {
+    /// Direction numbers of every coordinate, as binary fractions
+    directions: Vec<[u32; SOBOL_BITS]>,
+}
+
+impl Sobol {
+    pub fn new(dimension: usize) -> Self {
+        let mut directions = Vec::with_capacity(dimension);
+        let mut polynomials = primitive_polynomials();
+
+        if dimension > 0 {
+            // The van der Corput sequence in base 2
+            directions.push(std::array::from_fn(|k| 1 << (SOBOL_BITS - 1 - k)));
+        }
+
+        while directions.len() < dimension {
+            let (polynomial, degree) = polynomials.next().expect("enough primitive polynomials below degree 32");
+
+            // m_k = 2 a_1 m_{k-1} ^ 4 a_2 m_{k-2} ^ ... ^ 2^s m_{k-s} ^ m_{k-s}, with m_1 = ... = m_s = 1
+            let mut m = [1u32; SOBOL_BITS];
+            for k in degree..SOBOL_BITS {
+                let mut value = m[k - degree] ^ (m[k - degree] << degree);
+                for i in 1..degree {
+                    if (polynomial >> (degree - i)) & 1 == 1 {
+                        value ^= m[k - i] << i;
+                    }
+                }
+                m[k] = value;
+            }
+
+            directions.push(std::array::from_fn(|k| m[k] << (SOBOL_BITS - 1 - k)));
+        }
+
+        Self { directions }
+    }
+
+    /// Coordinates of the point at `index` as binary fractions, or `None`
+    /// from [`SOBOL_PERIOD`] on, where the sequence would repeat itself
+    pub fn bits(&self, index: u64) -> Option<Vec<u32>> {
+        if index >= SOBOL_PERIOD {
+            return None;
+        }
+
+        let gray = index ^ (index >> 1);
+
+        let bits = self
+            .directions
+            .iter()
+            .map(|directions| {
+                (0..SOBOL_BITS)
+                    .filter(|&k| (gray >> k) & 1 == 1)
+                    .fold(0, |x, k| x ^ directions[k])
+            })
+            .collect();
+
+        Some(bits)
+    }
+
+    pub fn point(&self, index: u64) -> Option<Vec<f64>> {
+        Some(self.bits(index)?.into_iter().map(to_unit).collect())
+    }
+}
+
+fn to_unit(bits: u32) -> f64 {
+    bits as f64 / (1u64 << SOBOL_BITS) as f64
+}
+
+/// Primitive polynomials over GF(2) and their degrees, by increasing degree,
+/// with bit `i` holding the coefficient of `x^i`.
+fn primitive_polynomials() -> impl Iterator<Item = (u64, usize)> {
+    (1..SOBOL_BITS).flat_map(|degree| {
+        let order = (1u64 << degree) - 1;
+        let factors = prime_factors(order);
+
+        // Both the leading and the constant coefficient are 1
+        ((1u64 << degree) | 1..1u64 << (degree + 1))
+            .step_by(2)
+            .filter(move |&polynomial| {
+                let power = |exponent| pow_mod(0b10, exponent, polynomial, degree);
+                power(order) == 1 && factors.iter().all(|&q| power(order / q) != 1)
+            })
+            .map(move |polynomial| (polynomial, degree))
+    })
+}
+
+fn prime_factors(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+
+    factors
+}
+
+/// Remainder of `a`, of degree below `2 * degree`, divided by `modulus`
+fn reduce(mut a: u64, modulus: u64, degree: usize) -> u64 {
+    for i in (degree..2 * degree).rev() {
+        if (a >> i) & 1 == 1 {
+            a ^= modulus << (i - degree);
+        }
+    }
+    a
+}
+
+fn pow_mod(base: u64, mut exponent: u64, modulus: u64, degree: usize) -> u64 {
+    let multiply = |a: u64, b: u64| {
+        let product = (0..degree).filter(|&i| (b >> i) & 1 == 1).fold(0, |product, i| product ^ (a << i));
+        reduce(product, modulus, degree)
+    };
+
+    let mut base = reduce(base, modulus, degree);
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base);
+        }
+        base = multiply(base, base);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// Points of the unit hypercube for the initial population of one run,
+/// continuing the low-discrepancy sequences from batch to batch.
+pub(crate) struct UnitPoints {
+    dimension: usize,
+    sequence: Sequence,
+    next: u64,
+}
+
+enum Sequence {
+    LatinHypercube,
+    Halton(Halton, Vec<f64>),
+    Sobol(Sobol, Vec<u32>),
+    Opposition,
+}
+
+impl UnitPoints {
+    /// `None` for [`Initialization::Random`], which leaves sampling to the problem
+    pub(crate) fn new<R: Rng + ?Sized>(initialization: Initialization, dimension: usize, rng: &mut R) -> Option<Self> {
+        let sequence = match initialization {
+            Initialization::Random => return None,
+            Initialization::LatinHypercube => Sequence::LatinHypercube,
+            Initialization::Halton => {
+                Sequence::Halton(Halton::new(dimension), (0..dimension).map(|_| rng.gen()).collect())
+            }
+            Initialization::Sobol => {
+                Sequence::Sobol(Sobol::new(dimension), (0..dimension).map(|_| rng.gen()).collect())
+            }
+            Initialization::Opposition => Sequence::Opposition,
+        };
+
+        Some(Self { dimension, sequence, next: 0 })
+    }
+
+    /// `count` points, followed by their opposites for [`Initialization::Opposition`],
+    /// or `None` if the sequence runs out of points
+    pub(crate) fn batch<R: Rng + ?Sized>(&mut self, count: usize, rng: &mut R) -> Option<Vec<Vec<f64>>> {
+        let indices = self.next..self.next + count as u64;
+        self.next += count as u64;
+
+        let points = match &self.sequence {
+            Sequence::LatinHypercube => latin_hypercube(count, self.dimension, rng),
+            Sequence::Halton(halton, shift) => indices
+                .map(|index| halton.point(index).iter().zip(shift).map(|(x, s)| (x + s).fract()).collect())
+                .collect(),
+            Sequence::Sobol(sobol, shift) => indices
+                .map(|index| Some(sobol.bits(index)?.iter().zip(shift).map(|(x, s)| to_unit(x ^ s)).collect()))
+                .collect::<Option<_>>()?,
+            Sequence::Opposition => {
+                let points = (0..count)
+                    .map(|_| (0..self.dimension).map(|_| rng.gen::<f64>()).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                let opposites = points.iter().map(|point| point.iter().map(|x| 1.0 - x).collect()).collect::<Vec<_>>();
+
+                points.into_iter().chain(opposites).collect()
+            }
+        };
+
+        Some(points)
+    }
+}
@@ -0,0 +1,42 @@
+pub mod algorithm;
+pub mod archive;
+pub mod benchmark;
+pub mod config;
+pub mod directions;
+pub mod distributed;
+pub mod dominance;
+mod engine;
+pub mod experiment;
+pub mod evaluation;
+pub mod ga;
+pub mod genome;
+pub mod history;
+pub mod individual;
+pub mod initialization;
+pub mod live;
+pub mod metrics;
+pub mod moead;
+pub mod nsga2;
+pub mod nsga3;
+pub mod output;
+pub mod plot;
+pub mod problems;
+pub mod sms_emoa;
+pub mod solution;
+pub mod spea2;
+pub mod stats;
+
+pub use crate::algorithm::{Algorithm, Build};
+pub use crate::archive::{Archive, ArchivePolicy};
+pub use crate::config::{Config, ConfigBuilder, ConfigError};
+pub use crate::evaluation::{Evaluation, EvaluationError, Evaluator, FailurePolicy, LocalEvaluator, RunError, RunStatistics};
+pub use crate::ga::{Ga, GaParams};
+pub use crate::history::RunHistory;
+pub use crate::individual::Individual;
+pub use crate::initialization::Initialization;
+pub use crate::moead::{Moead, MoeadParams};
+pub use crate::nsga2::{Replacement, NSGA2};
+pub use crate::nsga3::Survival;
+pub use crate::sms_emoa::SmsEmoa;
+pub use crate::solution::{Portable, Problem, Solution, Standalone};
+pub use crate::spea2::{Spea2, Spea2Params};
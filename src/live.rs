@@ -0,0 +1,157 @@
+//! Terminal display of a running optimization, redrawn in place.
+
+use std::io::{self, Write};
+
+use crate::history::{GenerationRecord, Observer};
+use crate::individual::{pareto_front, Individual};
+use crate::metrics::hypervolume;
+
+/// Braille patterns address a 2 x 4 grid of dots per character cell.
+const DOT_BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Redraws the first front every `every` generations, together with the
+/// generation number, its hypervolume and the feasible ratio.
+///
+/// The hypervolume reference point is fixed at the first redraw, slightly
+/// beyond the worst objective values of the population at that time, so the
+/// values shown are comparable from one redraw to the next.
+pub struct LiveView {
+    every: usize,
+    width: usize,
+    height: usize,
+    writer: Box<dyn Write>,
+    reference: Option<Vec<f64>>,
+    lines_drawn: usize,
+}
+
+impl LiveView {
+    /// A 60 x 16 character view on standard error.
+    pub fn new(every: usize) -> Self {
+        Self {
+            every: every.max(1),
+            width: 60,
+            height: 16,
+            writer: Box::new(io::stderr()),
+            reference: None,
+            lines_drawn: 0,
+        }
+    }
+
+    /// Size of the plot area in characters.
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self
+    }
+
+    pub fn writer(mut self, writer: impl Write + 'static) -> Self {
+        self.writer = Box::new(writer);
+        self
+    }
+
+    fn draw<S>(&mut self, record: &GenerationRecord, population: &[Individual<S>]) -> io::Result<()> {
+        let front = pareto_front(population)
+            .into_iter()
+            .map(|individual| individual.objectives.clone())
+            .filter(|objectives| objectives.iter().all(|value| value.is_finite()))
+            .collect::<Vec<_>>();
+
+        let reference = self.reference.get_or_insert_with(|| reference_point(population));
+        let volume = hypervolume(&front, reference);
+
+        let mut lines = vec![format!(
+            "generation {:>6}   evaluations {:>8}   hypervolume {:<12.6}   feasible {:>5.1}%   front {}",
+            record.generation,
+            record.evaluations,
+            volume,
+            100.0 * record.feasible_ratio,
+            front.len()
+        )];
+
+        if front.first().is_some_and(|objectives| objectives.len() == 2) {
+            lines.extend(scatter(&front, self.width, self.height));
+        }
+
+        // Move back over the previous drawing and clear it before drawing again
+        if self.lines_drawn > 0 {
+            write!(self.writer, "\x1b[{}F\x1b[J", self.lines_drawn)?;
+        }
+
+        for line in &lines {
+            writeln!(self.writer, "{}", line)?;
+        }
+
+        self.lines_drawn = lines.len();
+        self.writer.flush()
+    }
+}
+
+impl<S> Observer<S> for LiveView {
+    fn observe(&mut self, record: &GenerationRecord, population: &[Individual<S>]) {
+        if record.generation.is_multiple_of(self.every) {
+            // A broken terminal should not end the run
+            let _ = self.draw(record, population);
+        }
+    }
+}
+
+/// 10% of the range beyond the worst finite value of every objective.
+fn reference_point<S>(population: &[Individual<S>]) -> Vec<f64> {
+    let objective_count = population.first().map_or(0, |i| i.objectives.len());
+
+    (0..objective_count)
+        .map(|m| {
+            let values = population.iter().map(|i| i.objectives[m]).filter(|value| value.is_finite());
+            let min = values.clone().fold(f64::INFINITY, f64::min);
+            let max = values.fold(f64::NEG_INFINITY, f64::max);
+
+            if min > max {
+                0.0
+            } else {
+                max + 0.1 * (max - min).max(f64::EPSILON)
+            }
+        })
+        .collect()
+}
+
+/// Braille scatter plot of two-objective points, framed and labelled with the
+/// ranges of both objectives.
+fn scatter(points: &[Vec<f64>], width: usize, height: usize) -> Vec<String> {
+    let range = |m: usize| {
+        let min = points.iter().map(|p| p[m]).fold(f64::INFINITY, f64::min);
+        let max = points.iter().map(|p| p[m]).fold(f64::NEG_INFINITY, f64::max);
+        (min, if max > min { max } else { min + 1.0 })
+    };
+    let (x_min, x_max) = range(0);
+    let (y_min, y_max) = range(1);
+
+    let mut cells = vec![vec![0u32; width]; height];
+
+    for point in points {
+        let dot_x = ((point[0] - x_min) / (x_max - x_min) * (2 * width - 1) as f64).round() as usize;
+        let dot_y = ((y_max - point[1]) / (y_max - y_min) * (4 * height - 1) as f64).round() as usize;
+
+        cells[dot_y / 4][dot_x / 2] |= DOT_BITS[dot_x % 2][dot_y % 4];
+    }
+
+    let mut lines = Vec::with_capacity(height + 3);
+    lines.push(format!("f2 {:<.4}", y_max));
+    lines.push(format!("┌{}┐", "─".repeat(width)));
+
+    for row in cells {
+        let row = row
+            .into_iter()
+            .map(|bits| char::from_u32(0x2800 + bits).unwrap_or(' '))
+            .collect::<String>();
+        lines.push(format!("│{}│", row));
+    }
+
+    lines.push(format!("└{}┘", "─".repeat(width)));
+
+    let left = format!("f2 {:.4}, f1 {:.4}", y_min, x_min);
+    let right = format!("f1 {:.4}", x_max);
+    let padding = (width + 2).saturating_sub(left.chars().count() + right.chars().count()).max(1);
+    lines.push(format!("{}{}{}", left, " ".repeat(padding), right));
+
+    lines
+}
@@ -1,87 +1,539 @@
-use crate::solution::Solution;
-use crate::nsga2::NSGA2;
-use crate::problems::*;
-
-mod solution;
-mod nsga2;
-mod problems;
-
-fn main() {
-    // Problem 1.2
-    let mut quadratic = NSGA2::<quadratic::Quadratic>::new(100, 1_000, 0.1, 0.9);
-
-    quadratic.run();
-
-    let final_population = quadratic.current_population();
-
-    // Filter feasible solutions
-    let feasible_solutions: Vec<&quadratic::Quadratic> = final_population
-        .iter()
-        .filter(|s| s.feasible())
-        .collect();
-
-    println!("{} of {} are feasible solutions to Problem 1.2:", feasible_solutions.len(), final_population.len());
-
-    // Display the final Pareto front
-    for solution in feasible_solutions {
-        println!(
-            "x1: {:.4}, x2: {:.4} == {:.4?}",
-            solution.x1,
-            solution.x2,
-            solution.fitness()
-        );
-    }
-
-    // Problem 1.3
-    let mut thirty = NSGA2::<thirty::Thirty>::new(100, 1_000, 0.1, 0.9);
-    
-    thirty.run();
-    
-    let final_population = thirty.current_population();
-    
-    // Filter feasible solutions
-    let feasible_solutions: Vec<&thirty::Thirty> = final_population
-        .iter()
-        .filter(|s| s.feasible())
-        .collect();
-
-    println!("\n{} of {} are feasible solutions to Problem 1.3:", feasible_solutions.len(), final_population.len());
-    
-    for solution in feasible_solutions {
-        for (i, &val) in solution.data.iter().enumerate() {
-            if i > 0 {
-                print!("\t");
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use nsga2::benchmark::{Configuration, Suite};
+use nsga2::directions::Directions;
+use nsga2::experiment::{Experiment, ExternalConfig, Method, ProblemConfig, WorkersConfig};
+use nsga2::moead::{MoeadParams, Scalarization};
+use nsga2::output::{write_csv, write_json};
+use nsga2::live::LiveView;
+use nsga2::problems::external::Format;
+use nsga2::problems::series::SeriesParams;
+use nsga2::problems::CATALOG;
+use nsga2::{ArchivePolicy, FailurePolicy, GaParams, Initialization, Replacement, Spea2Params, Survival};
+
+/// Penalty on the distance from the weight vector for `--scalarization pbi`
+const PBI_THETA: f64 = 5.0;
+
+const USAGE: &str = "\
+usage: nsga2 <command> [options]
+
+commands:
+  list                     list the built-in problems
+  run                      optimize one problem and write its Pareto front
+  bench                    run configurations over several seeds and compare
+                           their hypervolume and IGD
+
+options for run and bench:
+  --config PATH            experiment file (.toml or .json); options below
+                           override its settings
+  --problem NAME           problem to optimize (see `nsga2 list`)
+  --pop N                  population size (default 100)
+  --gens N                 number of generations (default 1000)
+  --max-evals N            stop once N evaluations have been spent
+  --time-limit SECONDS     stop once this much time has passed
+  --mutation P             mutation rate (default 0.1)
+  --crossover P            crossover rate (default 0.9)
+  --tournament K           tournament size (default 2)
+  --algorithm NAME         nsga2, moead, spea2, sms-emoa or, for single-objective
+                           problems, ga (default nsga2)
+  --steady-state K         NSGA-II: breed K children at a time, each replacing
+                           the worst individual right away
+  --divisions P            select by reference directions with P divisions per
+                           objective (NSGA-III) instead of crowding distance
+  --directions SPEC        NSGA-III with other reference directions, or the
+                           MOEA/D weight vectors: das-dennis:P, two-layer:P:Q
+                           (outer and inner divisions) or energy:N (N
+                           energy-spread directions)
+  --scalarization NAME     MOEA/D subproblems: tchebycheff, weighted-sum or pbi
+                           (default tchebycheff)
+  --neighbors T            MOEA/D neighbourhood size (default 20)
+  --elites N               GA: best individuals kept unchanged (default 1)
+  --archive SPEC           report an archive of the best solutions ever
+                           evaluated: unbounded, epsilon:E (one per box of
+                           side E) or crowding:N (at most N)
+  --init NAME              initial population: random, latin-hypercube, halton,
+                           sobol or opposition (default random)
+  --initial PATH           start the initial population with the solutions in
+                           PATH: a front.json or front.csv written by an earlier
+                           run, or a results.txt listing; they must fit the
+                           problem's variables and bounds
+  --seed N                 random seed; bench uses N, N+1, ... (default: random / 0)
+  --retries N              retries for failed evaluations (default 0)
+  --on-failure POLICY      resample, penalize or abort (default abort)
+
+options for run:
+  --out PATH               write results to PATH instead of stdout, as JSON if
+                           PATH ends in .json and as CSV otherwise
+  --out-dir DIR            write results, per-generation history.csv and the
+                           resolved experiment to DIR
+  --population             write the whole final population, not just the front
+  --plot                   also draw the results as an SVG plot in --out-dir
+  --live K                 show the first front on the terminal every K generations
+  --workers ADDR           listen on ADDR, such as 0.0.0.0:7878, and evaluate
+                           candidates on the nsga2-worker processes connecting
+                           there
+  --min-workers N          workers to wait for before the run starts (default 1)
+
+options for bench:
+  --suite PATH             benchmark suite (.toml or .json) listing problems
+                           and labelled configurations; replaces --config
+  --runs N                 number of seeds to run (default 10)
+  --out-dir DIR            write runs.csv, summary.csv and comparisons.csv to DIR
+
+options for test functions (zdt3, rastrigin, sphere, ackley, rosenbrock,
+griewank and schwefel):
+  --dimension N            number of variables (default 20 for rastrigin, 30
+                           for the others)
+
+options for dtlz1 and dtlz2:
+  --objectives N           number of objectives (default 3)
+  --dimension N            number of variables (default N + 4 for dtlz1 and
+                           N + 9 for dtlz2)
+
+options for reliability-redundancy problems (series, series_hikita,
+series_parallel, bridge, overspeed and variable_series):
+  --components PATH        component table (.toml or .json): system, alpha,
+                           beta, v, w, budgets and bounds
+
+options for --problem external:
+  --program PATH           executable evaluating one candidate per invocation
+  --args ARGS              whitespace-separated arguments for the program
+  --bounds LO:HI,...       bounds of every decision variable
+  --objectives N           number of objectives the program reports
+  --format json|text       input/output format (default json)
+  --timeout SECONDS        per-evaluation time limit (default 60)";
+
+/// Options that are switches rather than taking a value
+const SWITCHES: &[&str] = &["population", "plot"];
+
+struct Options {
+    values: HashMap<String, String>,
+    switches: HashSet<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut values = HashMap::new();
+        let mut switches = HashSet::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument {:?}", arg))?;
+
+            if SWITCHES.contains(&name) {
+                switches.insert(name.to_string());
+                continue;
+            }
+
+            let value = args.next().ok_or_else(|| format!("--{} needs a value", name))?;
+            values.insert(name.to_string(), value.clone());
+        }
+
+        Ok(Self { values, switches })
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    fn parse_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.get(name)
+            .map(|value| value.parse().map_err(|_| format!("invalid value for --{}: {:?}", name, value)))
+            .transpose()
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.contains(name)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.first().map(String::as_str) {
+        Some("list") => {
+            list();
+            Ok(())
+        }
+        Some("run") => Options::parse(&args[1..]).and_then(|options| run(&options)),
+        Some("bench") => Options::parse(&args[1..]).and_then(|options| bench(&options)),
+        Some("help" | "--help" | "-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() {
+    let width = CATALOG.iter().map(|(name, _)| name.len()).max().unwrap_or(0) + 2;
+
+    for (name, description) in CATALOG {
+        println!("{:<width$}{}", name, description);
+    }
+}
+
+/// The experiment described by `--config`, if any, with command-line options applied on top
+fn experiment(options: &Options) -> Result<Experiment, String> {
+    let mut experiment = match options.get("config") {
+        Some(path) => Experiment::load(path).map_err(|e| e.to_string())?,
+        None => Experiment::default(),
+    };
+
+    if let Some(name) = options.get("problem") {
+        // Keep the parameters from the file when it already describes this problem
+        if experiment.problem.name() != name {
+            experiment.problem =
+                ProblemConfig::named(name).ok_or_else(|| format!("unknown problem {:?}, see `nsga2 list`", name))?;
+        }
+    } else if options.get("config").is_none() {
+        return Err("--problem or --config is required".to_string());
+    }
+
+    if let ProblemConfig::External(external) = &mut experiment.problem {
+        apply_external(external, options)?;
+    }
+    if let ProblemConfig::Dtlz(params) = &mut experiment.problem {
+        if let Some(objectives) = options.parse_value::<usize>("objectives")? {
+            params.objectives = objectives;
+            params.variables = objectives.saturating_sub(1) + params.function.distance_variables();
+        }
+    }
+    if let Some(dimension) = options.parse_value("dimension")? {
+        match &mut experiment.problem {
+            ProblemConfig::Zdt3(params) => params.variables = dimension,
+            ProblemConfig::Dtlz(params) => params.variables = dimension,
+            ProblemConfig::Rastrigin(params) => params.dimension = dimension,
+            ProblemConfig::Function(params) => params.dimension = dimension,
+            _ => return Err("--dimension requires a test function".to_string()),
+        }
+    }
+    if let Some(path) = options.get("components") {
+        let components = SeriesParams::load(path).map_err(|e| e.to_string())?;
+        match &mut experiment.problem {
+            ProblemConfig::Series(params) => *params = components,
+            ProblemConfig::VariableSeries(params) => params.components = components,
+            _ => return Err("--components requires a reliability-redundancy problem".to_string()),
+        }
+    }
+
+    let algorithm = &mut experiment.algorithm;
+    if let Some(population_size) = options.parse_value("pop")? {
+        algorithm.population_size = population_size;
+    }
+    if let Some(seed) = options.parse_value("seed")? {
+        algorithm.seed = Some(seed);
+    }
+    if let Some(retries) = options.parse_value("retries")? {
+        algorithm.evaluation_retries = retries;
+    }
+    if let Some(name) = options.get("algorithm") {
+        algorithm.method = match name {
+            "nsga2" => Method::Nsga2,
+            // Keep the MOEA/D parameters from the file
+            "moead" if matches!(algorithm.method, Method::Moead(_)) => algorithm.method,
+            "moead" => Method::Moead(MoeadParams::default()),
+            "spea2" if matches!(algorithm.method, Method::Spea2(_)) => algorithm.method,
+            "spea2" => Method::Spea2(Spea2Params::default()),
+            "sms-emoa" => Method::SmsEmoa,
+            "ga" if matches!(algorithm.method, Method::Ga(_)) => algorithm.method,
+            "ga" => Method::Ga(GaParams::default()),
+            other => return Err(format!("unknown algorithm {:?}", other)),
+        };
+    }
+    if let Some(name) = options.get("scalarization") {
+        let Method::Moead(params) = &mut algorithm.method else {
+            return Err("--scalarization requires --algorithm moead".to_string());
+        };
+        params.scalarization = match name {
+            "tchebycheff" => Scalarization::Tchebycheff,
+            "weighted-sum" => Scalarization::WeightedSum,
+            "pbi" => Scalarization::Pbi { theta: PBI_THETA },
+            other => return Err(format!("unknown scalarization {:?}", other)),
+        };
+    }
+    if let Some(neighborhood_size) = options.parse_value("neighbors")? {
+        let Method::Moead(params) = &mut algorithm.method else {
+            return Err("--neighbors requires --algorithm moead".to_string());
+        };
+        params.neighborhood_size = neighborhood_size;
+    }
+    if let Some(elites) = options.parse_value("elites")? {
+        let Method::Ga(params) = &mut algorithm.method else {
+            return Err("--elites requires --algorithm ga".to_string());
+        };
+        params.elites = elites;
+    }
+    if let Some(archive) = options.get("archive") {
+        algorithm.archive = Some(parse_archive(archive)?);
+    }
+    if let Some(offspring) = options.parse_value("steady-state")? {
+        algorithm.replacement = Replacement::SteadyState { offspring };
+    }
+    if let Some(divisions) = options.parse_value("divisions")? {
+        algorithm.survival = Survival::ReferenceDirections {
+            directions: Directions::DasDennis { divisions },
+        };
+    }
+    if let Some(directions) = options.get("directions") {
+        let directions = parse_directions(directions)?;
+        match &mut algorithm.method {
+            Method::Moead(params) => params.weights = Some(directions),
+            Method::Nsga2 => algorithm.survival = Survival::ReferenceDirections { directions },
+            Method::Spea2(_) | Method::SmsEmoa | Method::Ga(_) => {
+                return Err("--directions requires --algorithm nsga2 or moead".to_string())
             }
-            print!("{:.4}", val);
         }
-        println!(
-            " == {:.4?}",
-            solution.fitness()
-        );
-    }
-    
-    // Problem 3.1
-    let mut series = NSGA2::<series::Series>::new(100, 1_000, 0.1, 0.9);
-    
-    series.run();
-    
-    let final_population = series.current_population();
-    
-    // Filter feasible solutions
-    let feasible_solutions: Vec<&series::Series> = final_population
-        .iter()
-        .filter(|s| s.feasible())
-        .collect();
-    
-    println!("\n{} of {} are feasible solutions to Problem 3.1:", feasible_solutions.len(), final_population.len());
-
-    for solution in feasible_solutions {
-        println!(
-            "r: {:.4?}, n: {:.4?} == {:.4?}",
-            solution.r,
-            solution.n,
-            solution.fitness()
-        );
+    }
+    if let Some(initialization) = options.get("init") {
+        algorithm.initialization = match initialization {
+            "random" => Initialization::Random,
+            "latin-hypercube" => Initialization::LatinHypercube,
+            "halton" => Initialization::Halton,
+            "sobol" => Initialization::Sobol,
+            "opposition" => Initialization::Opposition,
+            other => return Err(format!("unknown initialization {:?}", other)),
+        };
+    }
+    if let Some(path) = options.get("initial") {
+        algorithm.initial_solutions = Some(path.into());
+    }
+    if let Some(policy) = options.get("on-failure") {
+        algorithm.failure_policy = match policy {
+            "resample" => FailurePolicy::Resample,
+            "penalize" => FailurePolicy::Penalize,
+            "abort" => FailurePolicy::Abort,
+            other => return Err(format!("unknown failure policy {:?}", other)),
+        };
+    }
+
+    let operators = &mut experiment.operators;
+    if let Some(mutation_rate) = options.parse_value("mutation")? {
+        operators.mutation_rate = mutation_rate;
+    }
+    if let Some(crossover_rate) = options.parse_value("crossover")? {
+        operators.crossover_rate = crossover_rate;
+    }
+    if let Some(tournament_size) = options.parse_value("tournament")? {
+        operators.tournament_size = tournament_size;
+    }
+
+    let termination = &mut experiment.termination;
+    if let Some(max_generations) = options.parse_value("gens")? {
+        termination.max_generations = max_generations;
+    }
+    if let Some(max_evaluations) = options.parse_value("max-evals")? {
+        termination.max_evaluations = Some(max_evaluations);
+    }
+    if let Some(time_limit) = options.parse_value("time-limit")? {
+        termination.time_limit_seconds = Some(time_limit);
+    }
+
+    if let Some(directory) = options.get("out-dir") {
+        experiment.output.directory = Some(directory.into());
+    }
+    if options.switch("population") {
+        experiment.output.population = true;
+    }
+    if options.switch("plot") {
+        experiment.output.plot = true;
+    }
+
+    if let Some(listen) = options.get("workers") {
+        match &mut experiment.workers {
+            Some(workers) => workers.listen = listen.to_string(),
+            None => experiment.workers = Some(WorkersConfig::new(listen)),
+        }
+    }
+    if let Some(min_workers) = options.parse_value("min-workers")? {
+        let Some(workers) = &mut experiment.workers else {
+            return Err("--min-workers requires --workers".to_string());
+        };
+        workers.min_workers = min_workers;
+    }
+
+    experiment.validate().map_err(|e| e.to_string())?;
+    Ok(experiment)
+}
+
+/// `das-dennis:P`, `two-layer:P:Q` or `energy:N`
+fn parse_directions(spec: &str) -> Result<Directions, String> {
+    let invalid = || format!("invalid reference directions {:?}", spec);
+    let numbers = spec
+        .split(':')
+        .skip(1)
+        .map(|n| n.parse::<usize>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    match (spec.split(':').next(), numbers.as_slice()) {
+        (Some("das-dennis"), &[divisions]) => Ok(Directions::DasDennis { divisions }),
+        (Some("two-layer"), &[outer, inner]) => Ok(Directions::TwoLayer { outer, inner }),
+        (Some("energy"), &[count]) => Ok(Directions::Energy { count }),
+        _ => Err(invalid()),
+    }
+}
+
+/// `unbounded`, `epsilon:E` or `crowding:N`
+fn parse_archive(spec: &str) -> Result<ArchivePolicy, String> {
+    let invalid = || format!("invalid archive {:?}", spec);
+
+    match spec.split_once(':') {
+        None if spec == "unbounded" => Ok(ArchivePolicy::Unbounded),
+        Some(("epsilon", epsilon)) => Ok(ArchivePolicy::EpsilonDominance {
+            epsilon: epsilon.parse().map_err(|_| invalid())?,
+        }),
+        Some(("crowding", capacity)) => Ok(ArchivePolicy::Crowding {
+            capacity: capacity.parse().map_err(|_| invalid())?,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+fn apply_external(external: &mut ExternalConfig, options: &Options) -> Result<(), String> {
+    if let Some(bounds) = options.get("bounds") {
+        external.bounds = bounds
+            .split(',')
+            .map(|pair| {
+                let (low, high) = pair.split_once(':').ok_or_else(|| format!("invalid bounds {:?}", pair))?;
+                let low = low.trim().parse::<f64>().map_err(|_| format!("invalid bound {:?}", low))?;
+                let high = high.trim().parse::<f64>().map_err(|_| format!("invalid bound {:?}", high))?;
+                Ok((low, high))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+    }
+    if let Some(program) = options.get("program") {
+        external.program = program.into();
+    }
+    if let Some(objectives) = options.parse_value("objectives")? {
+        external.objectives = objectives;
+    }
+    if let Some(args) = options.get("args") {
+        external.args = args.split_whitespace().map(str::to_string).collect();
+    }
+    if let Some(format) = options.get("format") {
+        external.format = match format {
+            "json" => Format::Json,
+            "text" => Format::Text,
+            other => return Err(format!("unknown format {:?}", other)),
+        };
+    }
+    if let Some(timeout) = options.parse_value("timeout")? {
+        external.timeout_seconds = Some(timeout);
+    }
+
+    Ok(())
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let mut experiment = experiment(options)?;
+    experiment.resolve();
+
+    let live_view = options.parse_value::<usize>("live")?.map(LiveView::new);
+    let outcome = experiment.run_with(live_view).map_err(|e| e.to_string())?;
+
+    let individuals = if experiment.output.population {
+        outcome.population.iter().collect()
+    } else {
+        outcome.pareto_front()
+    };
+
+    eprintln!(
+        "{} evaluations ({} failed) in {:.2?} with seed {}, {} solutions",
+        outcome.statistics.evaluations,
+        outcome.statistics.failures,
+        outcome.elapsed,
+        experiment.algorithm.seed.unwrap_or_default(),
+        individuals.len()
+    );
+
+    if let Some(directory) = &experiment.output.directory {
+        experiment.write_results(&outcome).map_err(|e| e.to_string())?;
+        eprintln!("results written to {}", directory.display());
+    }
+
+    let result = match options.get("out") {
+        Some(path) => {
+            let json = Path::new(path).extension().is_some_and(|ext| ext == "json");
+            let mut file = File::create(path).map(BufWriter::new).map_err(|e| format!("{}: {}", path, e))?;
+
+            if json {
+                write_json(&mut file, &individuals)
+            } else {
+                write_csv(&mut file, &individuals)
+            }
+            .and_then(|_| file.flush())
+        }
+        None if experiment.output.directory.is_none() => write_csv(&mut io::stdout().lock(), &individuals),
+        None => Ok(()),
+    };
+
+    result.map_err(|e| e.to_string())
+}
+
+fn bench(options: &Options) -> Result<(), String> {
+    let suite = match options.get("suite") {
+        Some(path) => {
+            let mut suite = Suite::load(path).map_err(|e| e.to_string())?;
+            if let Some(runs) = options.parse_value("runs")? {
+                suite.seeds = runs;
+            }
+            if let Some(seed) = options.parse_value("seed")? {
+                suite.first_seed = seed;
+            }
+            if let Some(directory) = options.get("out-dir") {
+                suite.output = Some(directory.into());
+            }
+            suite
+        }
+        None => {
+            let experiment = experiment(options)?;
+            Suite {
+                seeds: options.parse_value("runs")?.unwrap_or(10),
+                first_seed: experiment.algorithm.seed.unwrap_or(0),
+                problems: vec![experiment.problem],
+                configurations: vec![Configuration {
+                    label: "default".to_string(),
+                    algorithm: experiment.algorithm,
+                    operators: experiment.operators,
+                    termination: experiment.termination,
+                }],
+                output: experiment.output.directory,
+            }
+        }
+    };
+
+    println!("problem\tconfiguration\tseed\tseconds\tevaluations\tfront\thypervolume\tigd");
+
+    let report = suite
+        .run(|run| {
+            println!(
+                "{}\t{}\t{}\t{:.3}\t{}\t{}\t{:.6}\t{:.6}",
+                run.problem, run.configuration, run.seed, run.seconds, run.evaluations, run.front_size, run.hypervolume, run.igd
+            );
+        })
+        .map_err(|e| e.to_string())?;
+
+    println!("\n{}", report);
+
+    if let Some(directory) = &suite.output {
+        report.write(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+        eprintln!("report written to {}", directory.display());
     }
 
+    Ok(())
 }
@@ -0,0 +1,266 @@
+//! Quality indicators for approximation sets of minimization problems.
+
+use crate::dominance::dominates;
+
+/// Points of `points` not dominated by any other, without duplicates.
+pub fn nondominated(points: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut front: Vec<Vec<f64>> = Vec::new();
+
+    for (i, point) in points.iter().enumerate() {
+        let dominated = points.iter().any(|other| dominates(other, point));
+        let duplicate = points[..i].iter().any(|other| other == point);
+
+        if !dominated && !duplicate {
+            front.push(point.clone());
+        }
+    }
+
+    front
+}
+
+/// Component-wise minimum (ideal point) and maximum (nadir point) of `points`.
+pub fn bounds(points: &[Vec<f64>]) -> Option<(Vec<f64>, Vec<f64>)> {
+    let first = points.first()?;
+    let mut ideal = first.clone();
+    let mut nadir = first.clone();
+
+    for point in &points[1..] {
+        for (m, &value) in point.iter().enumerate() {
+            ideal[m] = ideal[m].min(value);
+            nadir[m] = nadir[m].max(value);
+        }
+    }
+
+    Some((ideal, nadir))
+}
+
+/// Map `points` linearly so that `ideal` becomes 0 and `nadir` becomes 1 in every objective.
+/// Objectives without spread are only shifted.
+pub fn normalize(points: &[Vec<f64>], ideal: &[f64], nadir: &[f64]) -> Vec<Vec<f64>> {
+    points
+        .iter()
+        .map(|point| {
+            point
+                .iter()
+                .zip(ideal.iter().zip(nadir))
+                .map(|(&value, (&low, &high))| {
+                    let range = high - low;
+                    if range > f64::EPSILON {
+                        (value - low) / range
+                    } else {
+                        value - low
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Volume of objective space dominated by `points` and bounded by `reference`.
+///
+/// Exact, by slicing along the last objective and recursing on the rest, which
+/// is fast enough for the front sizes and objective counts used here.
+pub fn hypervolume(points: &[Vec<f64>], reference: &[f64]) -> f64 {
+    let inside = points
+        .iter()
+        .filter(|point| point.iter().zip(reference).all(|(value, bound)| value < bound))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    hypervolume_of(nondominated(&inside), reference)
+}
+
+fn hypervolume_of(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    let m = reference.len();
+
+    if points.is_empty() {
+        return 0.0;
+    }
+
+    if m == 1 {
+        let best = points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+        return reference[0] - best;
+    }
+
+    if m == 2 {
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+        let mut volume = 0.0;
+        let mut ceiling = reference[1];
+
+        for point in &points {
+            if point[1] < ceiling {
+                volume += (reference[0] - point[0]) * (ceiling - point[1]);
+                ceiling = point[1];
+            }
+        }
+
+        return volume;
+    }
+
+    points.sort_by(|a, b| a[m - 1].total_cmp(&b[m - 1]));
+
+    let mut volume = 0.0;
+
+    for i in 0..points.len() {
+        let top = points.get(i + 1).map_or(reference[m - 1], |next| next[m - 1]);
+        let depth = top - points[i][m - 1];
+
+        if depth <= 0.0 {
+            continue;
+        }
+
+        let slice = points[..=i].iter().map(|p| p[..m - 1].to_vec()).collect::<Vec<_>>();
+        volume += hypervolume_of(nondominated(&slice), &reference[..m - 1]) * depth;
+    }
+
+    volume
+}
+
+/// Hypervolume that each of `points` alone adds to the set, bounded by `reference`.
+///
+/// Dominated points, duplicates and points outside the reference contribute nothing.
+pub fn hypervolume_contributions(points: &[Vec<f64>], reference: &[f64]) -> Vec<f64> {
+    let inside = |point: &Vec<f64>| point.iter().zip(reference).all(|(value, bound)| value < bound);
+
+    if reference.len() == 2 {
+        // Each point of a two-objective front owns the rectangle up to its neighbours
+        let mut order = (0..points.len()).filter(|&i| inside(&points[i])).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| points[a][0].total_cmp(&points[b][0]).then(points[a][1].total_cmp(&points[b][1])));
+
+        let mut contributions = vec![0.0; points.len()];
+        let mut ceiling = reference[1];
+        let mut front: Vec<usize> = Vec::new();
+        let mut duplicated = Vec::new();
+
+        for i in order {
+            if points[i][1] < ceiling {
+                ceiling = points[i][1];
+                front.push(i);
+            } else if front.last().is_some_and(|&last| points[last] == points[i]) {
+                // Sorted lexicographically, so copies are adjacent
+                duplicated.extend(front.last());
+            }
+        }
+
+        for (position, &i) in front.iter().enumerate() {
+            if duplicated.contains(&i) {
+                continue;
+            }
+
+            let right = front.get(position + 1).map_or(reference[0], |&next| points[next][0]);
+            let above = position.checked_sub(1).map_or(reference[1], |previous| points[front[previous]][1]);
+            contributions[i] = (right - points[i][0]) * (above - points[i][1]);
+        }
+
+        return contributions;
+    }
+
+    // What each point dominates beyond the parts the others dominate within its box
+    (0..points.len())
+        .map(|i| {
+            let others = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, point)| point.clone())
+                .collect::<Vec<_>>();
+
+            shared_contribution(&points[i], &points[i], &others, reference)
+        })
+        .collect()
+}
+
+/// Volume dominated by both `a` and `b` but by none of `others`, bounded by
+/// `reference`: how much the contribution of `a` shrinks when `b` joins the
+/// set, or grows when it leaves. With `b` equal to `a` this is the
+/// contribution of `a` itself.
+pub fn shared_contribution(a: &[f64], b: &[f64], others: &[Vec<f64>], reference: &[f64]) -> f64 {
+    let corner = a.iter().zip(b).map(|(x, y)| x.max(*y)).collect::<Vec<_>>();
+
+    if corner.iter().zip(reference).any(|(value, bound)| value >= bound)
+        || others.iter().any(|point| point.iter().zip(&corner).all(|(value, c)| value <= c))
+    {
+        return 0.0;
+    }
+
+    // The parts of the others' boxes within the one of the corner
+    let limited = others
+        .iter()
+        .map(|point| point.iter().zip(&corner).map(|(value, c)| value.max(*c)).collect())
+        .collect::<Vec<_>>();
+    let volume = corner.iter().zip(reference).map(|(c, bound)| bound - c).product::<f64>();
+
+    (volume - hypervolume(&limited, reference)).max(0.0)
+}
+
+/// Inverted generational distance: the mean distance from each point of
+/// `reference_front` to its nearest neighbour in `front`.
+pub fn igd(front: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    if front.is_empty() {
+        return f64::INFINITY;
+    }
+
+    let total: f64 = reference_front
+        .iter()
+        .map(|target| {
+            front
+                .iter()
+                .map(|point| euclidean(point, target))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum();
+
+    total / reference_front.len() as f64
+}
+
+pub fn euclidean(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points of the plane x + y + z = 1 on a grid
+    fn plane(divisions: usize) -> Vec<Vec<f64>> {
+        let step = 1.0 / divisions as f64;
+        (0..=divisions)
+            .flat_map(|i| (0..=divisions - i).map(move |j| (i, j)))
+            .map(|(i, j)| vec![i as f64 * step, j as f64 * step, 1.0 - (i + j) as f64 * step])
+            .collect()
+    }
+
+    #[test]
+    fn contributions_are_what_the_hypervolume_loses_without_each_point() {
+        let reference = [1.1; 3];
+        let points = plane(5);
+        let total = hypervolume(&points, &reference);
+
+        for (i, contribution) in hypervolume_contributions(&points, &reference).into_iter().enumerate() {
+            let others = points.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, p)| p.clone()).collect::<Vec<_>>();
+            assert!((total - hypervolume(&others, &reference) - contribution).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn shared_contributions_track_a_point_joining_and_leaving() {
+        let reference = [1.1; 3];
+        let mut points = plane(4);
+        let joining = points.remove(7);
+
+        let before = hypervolume_contributions(&points, &reference);
+        let mut with = points.clone();
+        with.push(joining.clone());
+        let after = hypervolume_contributions(&with, &reference);
+
+        for (i, point) in points.iter().enumerate() {
+            let others = points.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, p)| p.clone()).collect::<Vec<_>>();
+            let shared = shared_contribution(point, &joining, &others, &reference);
+            assert!((before[i] - shared - after[i]).abs() < 1e-12);
+        }
+
+        let own = shared_contribution(&joining, &joining, &points, &reference);
+        assert!((own - after[points.len()]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,328 @@
+//! MOEA/D (Zhang and Li, 2007): the problem is decomposed into one scalar
+//! subproblem per weight vector, and neighbouring subproblems breed and
+//! improve together.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::{Algorithm, WithEngine};
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::directions::Directions;
+use crate::engine::Engine;
+use crate::evaluation::{RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::Individual;
+use crate::metrics::euclidean;
+use crate::nsga2::non_dominated_sort;
+use crate::solution::Solution;
+
+/// Stand-in for zero weights in the Tchebycheff function, so that every
+/// objective keeps some influence.
+const MIN_WEIGHT: f64 = 1e-6;
+
+/// How a weight vector turns the objectives into one value to minimize.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Scalarization {
+    /// Largest weighted distance to the ideal point
+    #[default]
+    Tchebycheff,
+    /// Weighted sum of the objectives; cannot reach non-convex parts of a front
+    WeightedSum,
+    /// Penalty-based boundary intersection: distance along the weight vector
+    /// plus `theta` times the distance from it
+    Pbi { theta: f64 },
+}
+
+impl Scalarization {
+    pub fn value(&self, objectives: &[f64], weights: &[f64], ideal: &[f64]) -> f64 {
+        match *self {
+            Scalarization::Tchebycheff => objectives
+                .iter()
+                .zip(weights)
+                .zip(ideal)
+                .map(|((f, w), z)| w.max(MIN_WEIGHT) * (f - z).abs())
+                .fold(f64::NEG_INFINITY, f64::max),
+            Scalarization::WeightedSum => objectives.iter().zip(weights).map(|(f, w)| w * f).sum(),
+            Scalarization::Pbi { theta } => {
+                let norm = weights.iter().map(|w| w * w).sum::<f64>().sqrt();
+                let translated = objectives.iter().zip(ideal).map(|(f, z)| f - z).collect::<Vec<_>>();
+
+                let along = translated.iter().zip(weights).map(|(t, w)| t * w).sum::<f64>() / norm;
+                let across = translated
+                    .iter()
+                    .zip(weights)
+                    .map(|(t, w)| (t - along * w / norm).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+
+                along.abs() + theta * across
+            }
+        }
+    }
+}
+
+/// Settings specific to [`Moead`]; everything else comes from [`Config`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MoeadParams {
+    pub scalarization: Scalarization,
+    /// Number of closest weight vectors, itself included, forming a subproblem's neighbourhood
+    pub neighborhood_size: usize,
+    /// Probability of choosing parents from the neighbourhood rather than the whole population
+    pub neighborhood_probability: f64,
+    /// Most subproblems a single child may take over
+    pub max_replacements: usize,
+    /// Weight vectors, one per subproblem; as many energy-spread vectors as
+    /// the configured population size when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Directions>,
+}
+
+impl Default for MoeadParams {
+    fn default() -> Self {
+        Self {
+            scalarization: Scalarization::default(),
+            neighborhood_size: 20,
+            neighborhood_probability: 0.9,
+            max_replacements: 2,
+            weights: None,
+        }
+    }
+}
+
+impl MoeadParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.neighborhood_size < 2 {
+            return Err(format!("neighborhood_size must be at least 2, got {}", self.neighborhood_size));
+        }
+
+        if !(0.0..=1.0).contains(&self.neighborhood_probability) {
+            return Err(format!(
+                "neighborhood_probability must be within [0, 1], got {}",
+                self.neighborhood_probability
+            ));
+        }
+
+        if self.max_replacements < 1 {
+            return Err("max_replacements must be at least 1".to_string());
+        }
+
+        if let Scalarization::Pbi { theta } = self.scalarization {
+            if theta.is_nan() || theta < 0.0 {
+                return Err(format!("PBI theta must not be negative, got {}", theta));
+            }
+        }
+
+        if self.weights.is_some_and(|weights| weights.is_empty()) {
+            return Err("weight vectors must not be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Moead<S: Solution> {
+    engine: Engine<S>,
+    params: MoeadParams,
+    /// Current solution of every subproblem, indexed like `weights`
+    population: Vec<Individual<S>>,
+    weights: Vec<Vec<f64>>,
+    neighborhoods: Vec<Vec<usize>>,
+    ideal: Vec<f64>,
+}
+
+impl<S: Solution> Moead<S> {
+    /// Use `params` instead of the default [`MoeadParams`].
+    pub fn with_params(mut self, params: MoeadParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn params(&self) -> &MoeadParams {
+        &self.params
+    }
+
+    /// Weight vector of every subproblem, indexed like the population
+    pub fn weights(&self) -> &[Vec<f64>] {
+        &self.weights
+    }
+
+    /// Evaluate the initial population, then set up one subproblem per member.
+    /// The number of weight vectors is only known once the objective count is,
+    /// so the population is topped up or cut down to match afterwards; the
+    /// top-up continues the initialization rather than starting it over.
+    fn set_up_subproblems(&mut self) -> Result<(), RunError> {
+        let population_size = self.engine.config.population_size();
+        self.population = self.engine.initial_population(population_size)?;
+
+        let objective_count = self.population[0].objectives.len();
+        let directions = self.params.weights.unwrap_or(Directions::Energy { count: population_size });
+        self.weights = directions.generate(objective_count);
+
+        if self.weights.len() > self.population.len() {
+            let missing = self.weights.len() - self.population.len();
+            let extra = self.engine.initial_population(missing)?;
+            self.population.extend(extra);
+        }
+        self.population.truncate(self.weights.len());
+
+        let neighborhood_size = self.params.neighborhood_size.min(self.weights.len());
+        self.neighborhoods = self
+            .weights
+            .iter()
+            .map(|weight| {
+                let mut closest = (0..self.weights.len()).collect::<Vec<_>>();
+                closest.sort_by(|&a, &b| {
+                    euclidean(weight, &self.weights[a]).total_cmp(&euclidean(weight, &self.weights[b]))
+                });
+                closest.truncate(neighborhood_size);
+                closest
+            })
+            .collect();
+
+        self.ideal = vec![f64::INFINITY; objective_count];
+        for objectives in self.population.iter().map(|i| i.objectives.clone()).collect::<Vec<_>>() {
+            self.update_ideal(&objectives);
+        }
+
+        Ok(())
+    }
+
+    fn update_ideal(&mut self, objectives: &[f64]) {
+        for (z, &f) in self.ideal.iter_mut().zip(objectives) {
+            if f.is_finite() {
+                *z = z.min(f);
+            }
+        }
+    }
+
+    /// Whether `child` is at least as good a solution of subproblem `j` as its current one.
+    /// Feasibility comes first, as in the crowded comparison of NSGA-II.
+    fn improves(&self, child: &Individual<S>, j: usize) -> bool {
+        let current = &self.population[j];
+
+        if child.feasible != current.feasible {
+            return child.feasible;
+        }
+
+        let scalarization = self.params.scalarization;
+        scalarization.value(&child.objectives, &self.weights[j], &self.ideal)
+            <= scalarization.value(&current.objectives, &self.weights[j], &self.ideal)
+    }
+}
+
+impl<S: Solution> WithEngine<S> for Moead<S> {
+    fn from_engine(engine: Engine<S>) -> Self {
+        Self {
+            engine,
+            params: MoeadParams::default(),
+            population: Vec::new(),
+            weights: Vec::new(),
+            neighborhoods: Vec::new(),
+            ideal: Vec::new(),
+        }
+    }
+
+    fn engine_mut(&mut self) -> &mut Engine<S> {
+        &mut self.engine
+    }
+}
+
+impl<S: Solution> Algorithm<S> for Moead<S> {
+    fn initialize(&mut self) -> Result<(), RunError> {
+        self.engine.start();
+        self.set_up_subproblems()?;
+
+        let fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &fronts);
+        Ok(())
+    }
+
+    /// Breed one child per subproblem. The children are evaluated together, so
+    /// that batch evaluators can work on them in parallel, and then offered to
+    /// the neighbourhoods one after another.
+    fn step(&mut self) -> Result<(), RunError> {
+        let mut pools = Vec::with_capacity(self.population.len());
+        let mut children = Vec::with_capacity(self.population.len());
+
+        for subproblem in 0..self.population.len() {
+            let neighbourhood = self.engine.rng.gen_bool(self.params.neighborhood_probability);
+            let pool = if neighbourhood {
+                self.neighborhoods[subproblem].clone()
+            } else {
+                (0..self.population.len()).collect()
+            };
+
+            let mut parents = pool.choose_multiple(&mut self.engine.rng, 2);
+            let first = *parents.next().unwrap_or(&subproblem);
+            let second = *parents.next().unwrap_or(&first);
+
+            let (parent1, parent2) = (&self.population[first].solution, &self.population[second].solution);
+            children.push(self.engine.breed(parent1, parent2));
+            pools.push(pool);
+        }
+
+        let children = self.engine.evaluate_each(children)?;
+
+        for (child, mut pool) in children.into_iter().zip(pools) {
+            let Some(child) = child else {
+                continue;
+            };
+
+            self.update_ideal(&child.objectives);
+
+            pool.shuffle(&mut self.engine.rng);
+            let mut replaced = 0;
+
+            for j in pool {
+                if replaced >= self.params.max_replacements {
+                    break;
+                }
+
+                if self.improves(&child, j) {
+                    self.population[j] = child.clone();
+                    replaced += 1;
+                }
+            }
+        }
+
+        self.engine.generation += 1;
+        let fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &fronts);
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.engine.finished()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.engine.add_observer(observer);
+    }
+
+    fn population(&self) -> &[Individual<S>] {
+        &self.population
+    }
+
+    fn archive(&self) -> Option<&Archive<S>> {
+        self.engine.archive.as_ref()
+    }
+
+    fn best(&self) -> Option<&Individual<S>> {
+        self.engine.best.as_ref()
+    }
+
+    fn config(&self) -> &Config {
+        &self.engine.config
+    }
+
+    fn statistics(&self) -> &RunStatistics {
+        &self.engine.statistics
+    }
+
+    fn history(&self) -> &RunHistory {
+        &self.engine.history
+    }
+}
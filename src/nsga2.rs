@@ -1,160 +1,322 @@
-
 use rand::prelude::*;
-use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::{Algorithm, WithEngine};
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::engine::Engine;
+use crate::evaluation::{RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::Individual;
+use crate::nsga3::{niching, Survival};
 use crate::solution::Solution;
 
+/// Draws a member of the initial population
+pub type Sampler<S> = Box<dyn FnMut(&mut StdRng) -> S>;
+
+/// How offspring make their way into the population.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Replacement {
+    /// Breed a whole batch of offspring, then select the next population from
+    /// parents and offspring together
+    #[default]
+    Generational,
+    /// Breed `offspring` children at a time and let each replace the worst
+    /// individual right away, so that good children can breed sooner
+    SteadyState { offspring: usize },
+}
+
 pub struct NSGA2<S: Solution> {
-    population_size: usize,
-    max_generations: usize,
-    mutation_rate: f64,
-    crossover_rate: f64,
-    population: Vec<S>,
+    engine: Engine<S>,
+    population: Vec<Individual<S>>,
+    /// Non-dominated fronts of `population`, best first
+    fronts: Vec<Vec<usize>>,
+    /// Reference directions for [`Survival::ReferenceDirections`], once the objective count is known
+    directions: Vec<Vec<f64>>,
+    /// Lowest value of each objective seen by reference-direction survival during this run
+    ideal: Vec<f64>,
 }
 
 impl<S: Solution> NSGA2<S> {
-    pub fn new(population_size: usize, max_generations: usize, mutation_rate: f64, crossover_rate: f64) -> Self {
-        let mut population = Vec::with_capacity(population_size);
+    /// Rank and crowding distance of every individual, indexed like the population
+    fn ranks_and_distances(&self) -> (Vec<usize>, Vec<f64>) {
+        let mut ranks = vec![0; self.population.len()];
+        let mut distances = vec![0.0; self.population.len()];
+
+        for (rank, front) in self.fronts.iter().enumerate() {
+            // NSGA-III breaks rank ties at random instead of by crowding
+            let front_distances = match self.engine.config.survival() {
+                Survival::CrowdingDistance => crowding_distance(&self.population, front),
+                Survival::ReferenceDirections { .. } => vec![0.0; front.len()],
+            };
 
-        for _ in 0..population_size {
-            population.push(S::random());
+            for (&index, distance) in front.iter().zip(front_distances) {
+                ranks[index] = rank;
+                distances[index] = distance;
+            }
         }
 
-        Self {
-            population_size,
-            max_generations,
-            mutation_rate,
-            crossover_rate,
-            population,
-        }
+        (ranks, distances)
     }
 
-    pub fn run(&mut self) {
-        let objective_count = self.population[0].fitness().len();
+    fn generational_step(&mut self) -> Result<(), RunError> {
+        let population_size = self.engine.config.population_size();
+        let (ranks, distances) = self.ranks_and_distances();
 
-        for _ in 0..self.max_generations {
-            let fronts = non_dominated_sort(&self.population);
+        let mating_pool = self.tournament_selection(population_size, &ranks, &distances);
+        let offspring = self.engine.offspring(&mating_pool, mating_pool.len().div_ceil(2), 2)?;
 
-            let distances = fronts.iter().flat_map(|front| {
-                let front_solutions = front.iter().map(|&index| self.population[index].clone()).collect::<Vec<_>>();
-                crowding_distance(&front_solutions, objective_count)
-            }).collect::<Vec<_>>();
+        let mut combined_population = std::mem::take(&mut self.population);
+        combined_population.extend(offspring);
 
-            let mating_pool = self.tournament_selection(&fronts, &distances);
-            let offspring = self.crossover_and_mutate(&mating_pool);
+        let fronts = non_dominated_sort(&combined_population);
 
-            let mut combined_population = self.population.clone();
-            combined_population.extend(offspring);
+        let mut selected = Vec::with_capacity(population_size);
 
-            let fronts = non_dominated_sort(&combined_population);
+        for front in fronts {
+            if selected.len() + front.len() <= population_size {
+                selected.extend(front);
+                continue;
+            }
 
-            let mut new_population = Vec::with_capacity(self.population_size);
+            let remaining = population_size - selected.len();
 
-            for front in fronts {
-                let front_solutions = front.iter().map(|&i| combined_population[i].clone()).collect::<Vec<_>>();
-                let distance = crowding_distance(&front_solutions, objective_count);
+            match self.engine.config.survival() {
+                Survival::CrowdingDistance => {
+                    // Sort by crowding distance if this front is larger than remaining space for population
+                    let distance = crowding_distance(&combined_population, &front);
+                    let mut front_with_distances = front.into_iter().zip(distance).collect::<Vec<_>>();
+                    front_with_distances.sort_by(|a, b| b.1.total_cmp(&a.1));
 
-                if new_population.len() + front.len() <= self.population_size {
-                    new_population.extend(front_solutions);
-                    continue;
+                    selected.extend(front_with_distances.into_iter().take(remaining).map(|(i, _)| i));
                 }
-
-                // Sort by crowding distance if this front is larger than remaining space for population
-                let mut front_with_distances = front_solutions.iter().zip(distance).collect::<Vec<_>>();
-                front_with_distances.sort_by(|a, b| b.1.total_cmp(&a.1));
-
-                for (solution, _) in front_with_distances {
-                    if new_population.len() < self.population_size {
-                        new_population.push(solution.clone());
-                    } else {
-                        break;
-                    }
+                Survival::ReferenceDirections { .. } => {
+                    let chosen = niching(
+                        &combined_population,
+                        &selected,
+                        &front,
+                        remaining,
+                        &self.directions,
+                        &mut self.ideal,
+                        &mut self.engine.rng,
+                    );
+                    selected.extend(chosen);
                 }
             }
+            break;
+        }
+
+        let mut combined_population = combined_population.into_iter().map(Some).collect::<Vec<_>>();
+        self.population = selected
+            .into_iter()
+            .filter_map(|i| combined_population[i].take())
+            .collect();
 
-            self.population = new_population;
+        self.fronts = non_dominated_sort(&self.population);
+        Ok(())
+    }
+
+    /// Breed `offspring` children and let each one in turn join the population
+    /// and push out its worst member. The fronts are kept up to date as that
+    /// happens instead of being sorted from scratch.
+    fn steady_state_step(&mut self, offspring: usize) -> Result<(), RunError> {
+        let (ranks, distances) = self.ranks_and_distances();
+        let mating_pool = self.tournament_selection(2 * offspring, &ranks, &distances);
+
+        for child in self.engine.offspring(&mating_pool, offspring, 2)? {
+            self.population.push(child);
+            insert_into_fronts(&self.population, &mut self.fronts, self.population.len() - 1);
+            self.remove_worst();
         }
+
+        Ok(())
     }
 
-    pub fn current_population(&self) -> &Vec<S> {
-        &self.population
+    /// Drop the member of the last front that contributes least to its spread,
+    /// by crowding distance or by reference directions as configured.
+    fn remove_worst(&mut self) {
+        let Some(last_front) = self.fronts.last() else {
+            return;
+        };
+
+        let position = match self.engine.config.survival() {
+            _ if last_front.len() == 1 => 0,
+            Survival::CrowdingDistance => {
+                let distances = crowding_distance(&self.population, last_front);
+                (0..last_front.len())
+                    .min_by(|&a, &b| distances[a].total_cmp(&distances[b]))
+                    .unwrap_or(0)
+            }
+            Survival::ReferenceDirections { .. } => {
+                let selected = self.fronts[..self.fronts.len() - 1].concat();
+                let chosen = niching(
+                    &self.population,
+                    &selected,
+                    last_front,
+                    last_front.len() - 1,
+                    &self.directions,
+                    &mut self.ideal,
+                    &mut self.engine.rng,
+                );
+                last_front.iter().position(|i| !chosen.contains(i)).unwrap_or(0)
+            }
+        };
+
+        let last_front = self.fronts.last_mut().expect("checked above");
+        let worst = last_front.swap_remove(position);
+        if last_front.is_empty() {
+            self.fronts.pop();
+        }
+
+        // The last individual takes the place of the removed one
+        let moved = self.population.len() - 1;
+        self.population.swap_remove(worst);
+
+        if worst != moved {
+            for index in self.fronts.iter_mut().flatten() {
+                if *index == moved {
+                    *index = worst;
+                }
+            }
+        }
     }
 
-    fn tournament_selection(&self, fronts: &[Vec<usize>], distances: &Vec<f64>) -> Vec<S> {
-        let mut mating_pool = Vec::with_capacity(self.population_size);
-        let mut rng = thread_rng();
-
-        while mating_pool.len() < self.population_size {
-            let idx1 = rng.gen_range(0..self.population_size);
-            let idx2 = rng.gen_range(0..self.population_size);
-
-            let ind1 = &self.population[idx1];
-            let ind2 = &self.population[idx2];
-
-            // Determine the ranks of the solutions by finding which front they belong to
-            let rank1 = fronts.iter().position(|front| front.contains(&idx1)).unwrap();
-            let rank2 = fronts.iter().position(|front| front.contains(&idx2)).unwrap();
-
-            // Crowding distances
-            let dist1 = distances[idx1];
-            let dist2 = distances[idx2];
-
-            // Feasible solutions dominate infeasible ones so constraints are satisfied
-            let feasible1 = ind1.feasible();
-            let feasible2 = ind2.feasible();
-
-            let selected = if feasible1 && !feasible2 {
-                ind1.clone()
-            } else if !feasible1 && feasible2 {
-                ind2.clone()
-            } else if rank1 < rank2 {
-                ind1.clone()
-            } else if rank1 > rank2 {
-                ind2.clone()
-            } else if dist1 > dist2 {
-                ind1.clone()
-            } else {
-                ind2.clone()
-            };
+    fn tournament_selection(&mut self, count: usize, ranks: &[usize], distances: &[f64]) -> Vec<S> {
+        let population_size = self.population.len();
+        let mut mating_pool = Vec::with_capacity(count);
+
+        while mating_pool.len() < count {
+            let mut winner = self.engine.rng.gen_range(0..population_size);
+
+            for _ in 1..self.engine.config.tournament_size() {
+                let challenger = self.engine.rng.gen_range(0..population_size);
+
+                if self.beats(challenger, winner, ranks, distances) {
+                    winner = challenger;
+                }
+            }
 
-            mating_pool.push(selected);
+            mating_pool.push(self.population[winner].solution.clone());
         }
-        
+
         mating_pool
     }
 
-    fn crossover_and_mutate(&self, mating_pool: &[S]) -> Vec<S> {
-        let mut offspring = Vec::with_capacity(self.population_size);
-        let mut rng = thread_rng();
-        let dist = Uniform::new(0.0, 1.0);
+    /// Crowded comparison of two members of the current population
+    fn beats(&self, idx1: usize, idx2: usize, ranks: &[usize], distances: &[f64]) -> bool {
+        // Feasible solutions dominate infeasible ones so constraints are satisfied
+        let feasible1 = self.population[idx1].feasible;
+        let feasible2 = self.population[idx2].feasible;
 
-        for i in (0..mating_pool.len()).step_by(2) {
-            let parent1 = &mating_pool[i];
-            let parent2 = &mating_pool[(i + 1) % mating_pool.len()];
+        if feasible1 != feasible2 {
+            return feasible1;
+        }
 
-            let mut child = if rng.sample(dist) < self.crossover_rate {
-                parent1.crossover(parent2)
-            } else {
-                parent1.clone()
-            };
+        if ranks[idx1] != ranks[idx2] {
+            return ranks[idx1] < ranks[idx2];
+        }
 
-            if rng.sample(dist) < self.mutation_rate {
-                child.mutate();
-            }
+        distances[idx1] > distances[idx2]
+    }
+}
 
-            offspring.push(child);
+impl<S: Solution> WithEngine<S> for NSGA2<S> {
+    fn from_engine(engine: Engine<S>) -> Self {
+        Self {
+            population: Vec::with_capacity(engine.config.population_size()),
+            engine,
+            fronts: Vec::new(),
+            directions: Vec::new(),
+            ideal: Vec::new(),
         }
+    }
 
-        offspring
+    fn engine_mut(&mut self) -> &mut Engine<S> {
+        &mut self.engine
     }
 }
 
-fn non_dominated_sort<S: Solution>(population: &[S]) -> Vec<Vec<usize>> {
+impl<S: Solution> Algorithm<S> for NSGA2<S> {
+    fn initialize(&mut self) -> Result<(), RunError> {
+        self.engine.start();
+        self.population = self.engine.initial_population(self.engine.config.population_size())?;
+
+        if let Survival::ReferenceDirections { directions } = self.engine.config.survival() {
+            let objective_count = self.population.first().map_or(0, |i| i.objectives.len());
+            self.directions = directions.generate(objective_count);
+        }
+        self.ideal.clear();
+
+        self.fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &self.fronts);
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), RunError> {
+        match self.engine.config.replacement() {
+            Replacement::Generational => self.generational_step()?,
+            Replacement::SteadyState { offspring } => {
+                // At least as many evaluations per generation as in the generational mode
+                let steps = self.engine.config.population_size().div_ceil(2).div_ceil(offspring);
+
+                for _ in 0..steps {
+                    if self.engine.exhausted() {
+                        break;
+                    }
+
+                    self.steady_state_step(offspring)?;
+                }
+            }
+        }
+
+        self.engine.generation += 1;
+        self.engine.record(&self.population, &self.fronts);
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.engine.finished()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.engine.add_observer(observer);
+    }
+
+    fn population(&self) -> &[Individual<S>] {
+        &self.population
+    }
+
+    fn archive(&self) -> Option<&Archive<S>> {
+        self.engine.archive.as_ref()
+    }
+
+    fn best(&self) -> Option<&Individual<S>> {
+        self.engine.best.as_ref()
+    }
+
+    fn config(&self) -> &Config {
+        &self.engine.config
+    }
+
+    fn statistics(&self) -> &RunStatistics {
+        &self.engine.statistics
+    }
+
+    fn history(&self) -> &RunHistory {
+        &self.engine.history
+    }
+}
+
+/// Indices of `population` grouped into successive non-dominated fronts, best first
+pub fn non_dominated_sort<S>(population: &[Individual<S>]) -> Vec<Vec<usize>> {
     let population_size = population.len();
     let mut domination_counts = vec![0; population_size];
     let mut dominated_solutions = vec![Vec::new(); population_size];
     let mut fronts: Vec<Vec<usize>> = Vec::new();
     let mut first_front = Vec::new();
-    
+
     for p in 0..population_size {
         for q in 0..population_size {
             if p == q {
@@ -167,14 +329,14 @@ fn non_dominated_sort<S: Solution>(population: &[S]) -> Vec<Vec<usize>> {
                 domination_counts[p] += 1;
             }
         }
-        
+
         if domination_counts[p] == 0 {
             first_front.push(p);
         }
     }
 
     fronts.push(first_front);
-    
+
     let mut i = 0;
     while !fronts[i].is_empty() {
         let mut next_front = Vec::new();
@@ -199,17 +361,46 @@ fn non_dominated_sort<S: Solution>(population: &[S]) -> Vec<Vec<usize>> {
     fronts
 }
 
-fn crowding_distance<S: Solution>(front: &[S], objective_count: usize) -> Vec<f64> {
+/// Add `population[index]` to `fronts`, moving the members of its front that
+/// it dominates one front down, and so on for whoever those dominate in turn
+/// (Li et al., 2016).
+fn insert_into_fronts<S>(population: &[Individual<S>], fronts: &mut Vec<Vec<usize>>, index: usize) {
+    let newcomer = &population[index];
+    let mut level = fronts
+        .iter()
+        .position(|front| !front.iter().any(|&i| population[i].dominates(newcomer)))
+        .unwrap_or(fronts.len());
+    let mut moving = vec![index];
+
+    while !moving.is_empty() {
+        let Some(front) = fronts.get_mut(level) else {
+            fronts.push(moving);
+            break;
+        };
+
+        let (dominated, kept): (Vec<usize>, Vec<usize>) = front
+            .iter()
+            .partition(|&&i| moving.iter().any(|&m| population[m].dominates(&population[i])));
+
+        *front = kept;
+        front.extend(moving);
+        moving = dominated;
+        level += 1;
+    }
+}
+
+pub(crate) fn crowding_distance<S>(population: &[Individual<S>], front: &[usize]) -> Vec<f64> {
     let mut distances = vec![0.0; front.len()];
+    let objective_count = population[front[0]].objectives.len();
 
     for m in 0..objective_count {
         let mut sorted = front
             .iter()
             .enumerate()
-            .map(|(i, s)| (i, s.fitness()[m]))
+            .map(|(i, &index)| (i, population[index].objectives[m]))
             .collect::<Vec<_>>();
 
-        sorted.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
 
         distances[sorted[0].0] = f64::INFINITY;
         distances[sorted[sorted.len() - 1].0] = f64::INFINITY;
@@ -217,8 +408,9 @@ fn crowding_distance<S: Solution>(front: &[S], objective_count: usize) -> Vec<f6
         let min = sorted[0].1;
         let max = sorted[sorted.len() - 1].1;
 
-        if (max - min).abs() < f64::EPSILON {
-            continue; // Avoid division by zero
+        // Avoid division by zero, and by infinity from penalized individuals
+        if (max - min).abs() < f64::EPSILON || !(max - min).is_finite() {
+            continue;
         }
 
         for j in 1..sorted.len() - 1 {
@@ -0,0 +1,236 @@
+//! Reference-point based survival from NSGA-III (Deb and Jain, 2014), which
+//! keeps many-objective populations spread where crowding distance cannot.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::directions::Directions;
+use crate::individual::Individual;
+
+/// Weight of the other objectives when searching for extreme points, instead of zero.
+const EXTREME_POINT_EPSILON: f64 = 1e-6;
+
+/// How the last front that does not fit into the next population is truncated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Survival {
+    /// Keep the least crowded individuals (NSGA-II)
+    #[default]
+    CrowdingDistance,
+    /// Keep individuals close to under-represented reference directions (NSGA-III)
+    ReferenceDirections { directions: Directions },
+}
+
+/// Choose `count` members of `last_front` to join `selected`, filling the
+/// reference directions that the selected individuals represent least.
+///
+/// `ideal` is the lowest value of each objective seen so far; it is lowered
+/// to any candidate below it and kept by the caller across generations.
+pub(crate) fn niching<S, R: Rng + ?Sized>(
+    population: &[Individual<S>],
+    selected: &[usize],
+    last_front: &[usize],
+    count: usize,
+    directions: &[Vec<f64>],
+    ideal: &mut Vec<f64>,
+    rng: &mut R,
+) -> Vec<usize> {
+    let candidates = selected.iter().chain(last_front).copied().collect::<Vec<_>>();
+    let points = candidates.iter().map(|&i| population[i].objectives.as_slice()).collect::<Vec<_>>();
+    update_ideal(ideal, &points);
+    let normalized = normalize(&points, ideal);
+
+    // Nearest direction and distance to it for every candidate, selected ones first
+    let associations = normalized.iter().map(|point| associate(point, directions)).collect::<Vec<_>>();
+
+    let mut niche_counts = vec![0usize; directions.len()];
+    for &(direction, _) in &associations[..selected.len()] {
+        niche_counts[direction] += 1;
+    }
+
+    // Last-front members waiting for a place, by the direction they belong to
+    let mut members = vec![Vec::new(); directions.len()];
+    for (offset, &(direction, distance)) in associations[selected.len()..].iter().enumerate() {
+        members[direction].push((last_front[offset], distance));
+    }
+
+    let mut open = (0..directions.len()).collect::<Vec<_>>();
+    let mut chosen = Vec::with_capacity(count);
+
+    while chosen.len() < count && !open.is_empty() {
+        let least = open.iter().map(|&j| niche_counts[j]).min().unwrap_or(0);
+        let sparsest = open.iter().copied().filter(|&j| niche_counts[j] == least).collect::<Vec<_>>();
+        let direction = sparsest[rng.gen_range(0..sparsest.len())];
+
+        if members[direction].is_empty() {
+            open.retain(|&j| j != direction);
+            continue;
+        }
+
+        // An empty niche takes its closest member, a populated one any member
+        let position = if niche_counts[direction] == 0 {
+            members[direction]
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1 .1.total_cmp(&b.1 .1))
+                .map_or(0, |(position, _)| position)
+        } else {
+            rng.gen_range(0..members[direction].len())
+        };
+
+        chosen.push(members[direction].swap_remove(position).0);
+        niche_counts[direction] += 1;
+    }
+
+    chosen
+}
+
+/// Lower each objective of `ideal` to the smallest finite value among `points`,
+/// starting from infinity for objectives it does not cover yet.
+fn update_ideal(ideal: &mut Vec<f64>, points: &[&[f64]]) {
+    let objective_count = points.first().map_or(0, |point| point.len());
+    ideal.resize(objective_count.max(ideal.len()), f64::INFINITY);
+
+    for point in points {
+        for (low, &value) in ideal.iter_mut().zip(point.iter()) {
+            if value.is_finite() && value < *low {
+                *low = value;
+            }
+        }
+    }
+}
+
+/// Translate `points` so the `ideal` point is the origin and scale each
+/// objective by the intercept of the hyperplane through the extreme points,
+/// falling back to the nadir point when that hyperplane is degenerate.
+fn normalize(points: &[&[f64]], ideal: &[f64]) -> Vec<Vec<f64>> {
+    let objective_count = points.first().map_or(0, |point| point.len());
+
+    let translated = points
+        .iter()
+        .map(|point| point.iter().zip(ideal).map(|(value, low)| value - low).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let finite = translated
+        .iter()
+        .filter(|point| point.iter().all(|value| value.is_finite()))
+        .collect::<Vec<_>>();
+
+    let extremes = (0..objective_count)
+        .filter_map(|axis| {
+            finite
+                .iter()
+                .map(|point| {
+                    let scalarized = point
+                        .iter()
+                        .enumerate()
+                        .map(|(m, value)| value / if m == axis { 1.0 } else { EXTREME_POINT_EPSILON })
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    (point, scalarized)
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(point, _)| point.to_vec())
+        })
+        .collect::<Vec<_>>();
+
+    let intercepts = hyperplane_intercepts(&extremes)
+        .filter(|intercepts| intercepts.iter().all(|&a| a.is_finite() && a > EXTREME_POINT_EPSILON))
+        .unwrap_or_else(|| {
+            (0..objective_count)
+                .map(|m| {
+                    let nadir = finite.iter().map(|point| point[m]).fold(0.0, f64::max);
+                    if nadir > EXTREME_POINT_EPSILON {
+                        nadir
+                    } else {
+                        1.0
+                    }
+                })
+                .collect()
+        });
+
+    translated
+        .into_iter()
+        .map(|point| point.iter().zip(&intercepts).map(|(value, a)| value / a).collect())
+        .collect()
+}
+
+/// Axis intercepts of the hyperplane through `points`, one point per objective,
+/// or `None` if they do not span one.
+fn hyperplane_intercepts(points: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let n = points.len();
+
+    if n == 0 || points.iter().any(|point| point.len() != n) {
+        return None;
+    }
+
+    // Solve points * b = 1 by Gaussian elimination with partial pivoting;
+    // the plane is b . x = 1, crossing axis m at 1 / b[m]
+    let mut matrix = points.iter().map(|point| [point.as_slice(), &[1.0]].concat()).collect::<Vec<_>>();
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&a, &b| matrix[a][column].abs().total_cmp(&matrix[b][column].abs()))?;
+
+        if matrix[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+
+        matrix.swap(column, pivot);
+        let pivot_row = matrix[column].clone();
+
+        for (r, row) in matrix.iter_mut().enumerate() {
+            if r != column {
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+
+    Some((0..n).map(|m| matrix[m][m] / matrix[m][n]).collect())
+}
+
+/// Index of the reference direction nearest to `point` by perpendicular
+/// distance, and that distance.
+fn associate(point: &[f64], directions: &[Vec<f64>]) -> (usize, f64) {
+    directions
+        .iter()
+        .enumerate()
+        .map(|(j, direction)| (j, perpendicular_distance(point, direction)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, f64::INFINITY))
+}
+
+fn perpendicular_distance(point: &[f64], direction: &[f64]) -> f64 {
+    let norm = direction.iter().map(|w| w * w).sum::<f64>();
+    let projection = point.iter().zip(direction).map(|(p, w)| p * w).sum::<f64>() / norm;
+
+    point
+        .iter()
+        .zip(direction)
+        .map(|(p, w)| (p - projection * w).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ideal_point_keeps_the_lowest_values_of_earlier_generations() {
+        let mut ideal = Vec::new();
+        update_ideal(&mut ideal, &[&[1.0, 3.0], &[2.0, f64::INFINITY]]);
+        assert_eq!(ideal, [1.0, 3.0]);
+
+        // A later generation that lost the best value of the first objective
+        let later: [&[f64]; 2] = [&[2.0, 2.0], &[4.0, 1.0]];
+        update_ideal(&mut ideal, &later);
+        assert_eq!(ideal, [1.0, 1.0]);
+
+        let normalized = normalize(&later, &ideal);
+        assert_eq!(normalized[1][1], 0.0);
+        assert!(normalized.iter().all(|point| point.iter().all(|&value| value >= 0.0)));
+        assert!(normalized[0][0] > 0.0, "the first objective is measured from the old minimum");
+    }
+}
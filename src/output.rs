@@ -0,0 +1,278 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::individual::Individual;
+
+/// Write individuals as CSV: one column per objective, then feasibility, then
+/// the solution itself serialized as JSON.
+pub fn write_csv<W: Write + ?Sized, S: Serialize>(writer: &mut W, individuals: &[&Individual<S>]) -> io::Result<()> {
+    let objective_count = individuals.first().map_or(0, |i| i.objectives.len());
+
+    let mut header = (1..=objective_count).map(|m| format!("f{}", m)).collect::<Vec<_>>();
+    header.push("feasible".to_string());
+    header.push("solution".to_string());
+    writeln!(writer, "{}", header.join(","))?;
+
+    for individual in individuals {
+        let solution = serde_json::to_string(&individual.solution)?;
+
+        for value in &individual.objectives {
+            write!(writer, "{},", value)?;
+        }
+
+        // Quotes inside a quoted CSV field are escaped by doubling them
+        writeln!(writer, "{},\"{}\"", individual.feasible, solution.replace('"', "\"\""))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Record<'a, S> {
+    objectives: &'a [f64],
+    feasible: bool,
+    solution: &'a S,
+}
+
+/// Write individuals as a JSON array of `{objectives, feasible, solution}` records.
+pub fn write_json<W: Write + ?Sized, S: Serialize>(writer: &mut W, individuals: &[&Individual<S>]) -> io::Result<()> {
+    let records = individuals
+        .iter()
+        .map(|i| Record {
+            objectives: &i.objectives,
+            feasible: i.feasible,
+            solution: &i.solution,
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_writer_pretty(&mut *writer, &records)?;
+    writeln!(writer)
+}
+
+/// Solutions listed in `text`, as JSON values to be deserialized into the
+/// problem's solution type.
+///
+/// Reads back what [`write_json`] and [`write_csv`] write, by the file
+/// `extension`, and otherwise the lines of a `results.txt` listing, with
+/// everything from `==` on ignored, as are lines of any other shape:
+///
+/// - `name: value` pairs such as `x1: 1.5646, x2: 1.3948 == [17.5738, 24.7995]`,
+///   values possibly being lists like `r: [0.8, 0.9], n: [2, 3]`, where the
+///   `r` and `n` of reliability designs stand for their real and integer variables
+/// - bare values separated by whitespace, such as `0.8519  0.0001  0.0000`,
+///   for the `data` of a ZDT3 solution
+pub fn read_solutions(text: &str, extension: Option<&str>) -> Result<Vec<Value>, String> {
+    match extension {
+        Some("json") => {
+            let records: Vec<Value> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+
+            Ok(records
+                .into_iter()
+                .map(|mut record| match record.get_mut("solution") {
+                    Some(solution) => solution.take(),
+                    None => record,
+                })
+                .collect())
+        }
+        Some("csv") => {
+            let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+            if !lines.next().is_some_and(|header| header.trim_end().ends_with("solution")) {
+                return Err("expected a CSV file ending in a solution column".to_string());
+            }
+
+            lines
+                .enumerate()
+                .map(|(i, line)| {
+                    let solution = line
+                        .split_once('"')
+                        .and_then(|(_, quoted)| quoted.trim_end().strip_suffix('"'))
+                        .ok_or_else(|| format!("row {} has no quoted solution", i + 1))?;
+
+                    serde_json::from_str(&solution.replace("\"\"", "\"")).map_err(|e| format!("row {}: {}", i + 1, e))
+                })
+                .collect()
+        }
+        _ => Ok(text.lines().filter_map(named_values).collect()),
+    }
+}
+
+/// Names of `results.txt` variables and the solution fields they are read into
+const RESULT_FIELDS: [(&str, &str); 2] = [("r", "reals"), ("n", "integers")];
+
+/// A `results.txt` line as a JSON object, if it is one
+fn named_values(line: &str) -> Option<Value> {
+    let variables = line.split("==").next()?.trim();
+    if variables.is_empty() {
+        return None;
+    }
+
+    if !variables.contains(':') {
+        let data = variables.split_whitespace().map(number).collect::<Option<Vec<_>>>()?;
+        return Some(Value::Object(Map::from_iter([("data".to_string(), Value::Array(data))])));
+    }
+
+    top_level_pairs(variables)
+        .into_iter()
+        .map(|pair| {
+            let (name, value) = pair.split_once(':')?;
+            let name = name.trim();
+            let value = value.trim();
+
+            let identifier = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !identifier {
+                return None;
+            }
+
+            let value = match value.strip_prefix('[').and_then(|list| list.strip_suffix(']')) {
+                Some(list) if list.trim().is_empty() => Value::Array(Vec::new()),
+                Some(list) => Value::Array(list.split(',').map(number).collect::<Option<_>>()?),
+                None => number(value)?,
+            };
+
+            let field = RESULT_FIELDS.iter().find(|(short, _)| *short == name).map_or(name, |(_, field)| *field);
+            Some((field.to_string(), value))
+        })
+        .collect::<Option<Map<_, _>>>()
+        .map(Value::Object)
+}
+
+/// `text` split at the commas outside of brackets
+fn top_level_pairs(text: &str) -> Vec<&str> {
+    let mut pairs = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                pairs.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    pairs.push(&text[start..]);
+    pairs
+}
+
+/// A number as JSON, an integer where it is written as one
+fn number(text: &str) -> Option<Value> {
+    let text = text.trim();
+
+    match text.parse::<i64>() {
+        Ok(integer) => Some(Value::from(integer)),
+        Err(_) => text.parse::<f64>().ok().filter(|x| x.is_finite()).map(Value::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::problems::dtlz::{DtlzInstance, DtlzParams};
+    use crate::problems::flowshop::{FlowShopInstance, FlowShopParams};
+    use crate::problems::functions::{FunctionInstance, FunctionParams};
+    use crate::problems::knapsack::{KnapsackInstance, KnapsackParams};
+    use crate::problems::quadratic::QuadraticProblem;
+    use crate::problems::rastrigin::RastriginParams;
+    use crate::problems::series::{SeriesInstance, SeriesParams, VariableSeriesInstance, VariableSeriesParams};
+    use crate::problems::thirty::Zdt3Params;
+    use crate::problems::tsp::{TspInstance, TspParams};
+    use crate::solution::{Portable, Solution};
+
+    /// Solutions of `problem` written as JSON and CSV and read back into it
+    fn round_trip<P: Portable>(problem: P)
+    where
+        P::Solution: Serialize,
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let individuals = (0..5)
+            .map(|_| {
+                let solution = problem.random(&mut rng);
+                Individual {
+                    objectives: solution.fitness().unwrap(),
+                    feasible: solution.feasible(),
+                    solution,
+                }
+            })
+            .collect::<Vec<_>>();
+        let individuals = individuals.iter().collect::<Vec<_>>();
+        let expected = individuals.iter().map(|i| serde_json::to_value(&i.solution).unwrap()).collect::<Vec<_>>();
+
+        let mut json = Vec::new();
+        write_json(&mut json, &individuals).unwrap();
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &individuals).unwrap();
+
+        for (text, extension) in [(json, "json"), (csv, "csv")] {
+            let values = read_solutions(std::str::from_utf8(&text).unwrap(), Some(extension)).unwrap();
+            let read = values
+                .into_iter()
+                .map(|value| {
+                    let solution = problem.solution(serde_json::from_value(value).unwrap()).unwrap();
+                    serde_json::to_value(&solution).unwrap()
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(read, expected, "{}", extension);
+        }
+    }
+
+    #[test]
+    fn every_problem_reads_back_its_written_solutions() {
+        round_trip(QuadraticProblem);
+        round_trip(Zdt3Params::default());
+        round_trip(RastriginParams::default());
+        round_trip(Arc::new(FunctionInstance::new(FunctionParams::default())));
+        round_trip(Arc::new(DtlzInstance::new(DtlzParams::default())));
+        round_trip(Arc::new(SeriesInstance::new(SeriesParams::default())));
+        round_trip(Arc::new(VariableSeriesInstance::new(VariableSeriesParams::default())));
+        round_trip(Arc::new(KnapsackInstance::new(KnapsackParams::default())));
+        round_trip(Arc::new(TspInstance::new(TspParams::default())));
+        round_trip(Arc::new(FlowShopInstance::new(FlowShopParams::default())));
+    }
+
+    /// The single solution on `line` of a results.txt listing, read into `problem`
+    fn from_results<P: Portable>(problem: &P, line: &str) -> Result<P::Solution, String> {
+        let text = format!("3 of 100 are feasible solutions to Problem 1.2:\n{}\n\nProcess finished with exit code 0", line);
+        let values = read_solutions(&text, Some("txt"))?;
+        assert_eq!(values.len(), 1, "{:?}", values);
+
+        let genome = serde_json::from_value(values.into_iter().next().unwrap()).map_err(|e| e.to_string())?;
+        problem.solution(genome)
+    }
+
+    #[test]
+    fn reads_every_results_listing_format() {
+        let quadratic = from_results(&QuadraticProblem, "x1: 1.5646, x2: 1.3948 == [17.5738, 24.7995]").unwrap();
+        assert_eq!((quadratic.x1, quadratic.x2), (1.5646, 1.3948));
+
+        let zdt3 = Zdt3Params { variables: 3 };
+        let thirty = from_results(&zdt3, "0.8519\t0.0001  0.0000 == [0.8519, -0.7711]").unwrap();
+        assert_eq!(thirty.data, [0.8519, 0.0001, 0.0]);
+
+        let series = Arc::new(SeriesInstance::new(SeriesParams::default()));
+        let line = "r: [0.7800, 0.8200, 0.9000, 0.7100, 0.8500], n: [3, 2, 2, 3, 3] == [-0.9999, 0.0000]";
+        let design = from_results(&series, line).unwrap();
+        assert_eq!(design.r(), [0.78, 0.82, 0.9, 0.71, 0.85]);
+        assert_eq!(design.n(), [3, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn rejects_listed_solutions_that_do_not_fit_the_problem() {
+        assert!(from_results(&QuadraticProblem, "x1: 6.0, x2: 1.0 == [0, 0]").is_err());
+        assert!(from_results(&Zdt3Params { variables: 4 }, "0.5 0.5 0.5 == [0, 0]").is_err());
+
+        let series = Arc::new(SeriesInstance::new(SeriesParams::default()));
+        assert!(from_results(&series, "r: [0.8, 0.8], n: [2, 2] == [0, 0]").is_err());
+    }
+}
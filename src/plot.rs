@@ -0,0 +1,416 @@
+//! SVG rendering of populations: scatter plots for two objectives and parallel
+//! coordinates for any number of them.
+
+use std::fmt::Write;
+
+use crate::individual::Individual;
+use crate::nsga2::non_dominated_sort;
+
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+/// Room kept free for the legend right of the last parallel-coordinates axis
+const LEGEND_WIDTH: f64 = 100.0;
+
+/// Colors of successive fronts; later fronts reuse the last one.
+const RANK_COLORS: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+];
+const FEASIBLE_COLOR: &str = "#1f77b4";
+const INFEASIBLE_COLOR: &str = "#d62728";
+const REFERENCE_COLOR: &str = "#b0b0b0";
+
+/// How points are colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coloring {
+    /// Feasible and infeasible individuals in two colors
+    #[default]
+    Feasibility,
+    /// By non-dominated front, with infeasible individuals drawn hollow
+    Rank,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotOptions {
+    pub title: String,
+    pub width: f64,
+    pub height: f64,
+    pub coloring: Coloring,
+    /// Objectives on the horizontal and vertical axis of a scatter plot
+    pub axes: (usize, usize),
+    /// Known Pareto front drawn underneath the population, such as [`crate::problems::thirty::pareto_front`]
+    pub reference_front: Option<Vec<Vec<f64>>>,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            width: 640.0,
+            height: 480.0,
+            coloring: Coloring::default(),
+            axes: (0, 1),
+            reference_front: None,
+        }
+    }
+}
+
+/// Linear map from data coordinates onto a pixel range.
+#[derive(Clone, Copy)]
+struct Scale {
+    min: f64,
+    max: f64,
+    from: f64,
+    to: f64,
+}
+
+impl Scale {
+    fn new(values: impl IntoIterator<Item = f64>, from: f64, to: f64) -> Self {
+        let (mut min, mut max) = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+
+        if min > max {
+            (min, max) = (0.0, 1.0);
+        } else if (max - min).abs() < f64::EPSILON {
+            (min, max) = (min - 0.5, max + 0.5);
+        }
+
+        Self { min, max, from, to }
+    }
+
+    fn map(&self, value: f64) -> f64 {
+        self.from + (value - self.min) / (self.max - self.min) * (self.to - self.from)
+    }
+
+    /// Round-numbered tick positions inside the range, about `count` of them.
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        let raw = (self.max - self.min) / count as f64;
+        let magnitude = 10f64.powf(raw.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .into_iter()
+            .map(|factor| factor * magnitude)
+            .find(|&step| step >= raw)
+            .unwrap_or(10.0 * magnitude);
+
+        let first = (self.min / step).ceil() as i64;
+        let last = (self.max / step).floor() as i64;
+
+        (first..=last).map(|i| i as f64 * step).collect()
+    }
+}
+
+/// Render two objectives of `population` as a scatter plot.
+pub fn scatter<S>(population: &[&Individual<S>], options: &PlotOptions) -> String {
+    let (x_axis, y_axis) = options.axes;
+    let reference_front = options.reference_front.as_deref().unwrap_or_default();
+
+    let points = population
+        .iter()
+        .map(|individual| (individual.objectives[x_axis], individual.objectives[y_axis]))
+        .chain(reference_front.iter().map(|point| (point[x_axis], point[y_axis])))
+        .collect::<Vec<_>>();
+
+    let x = Scale::new(points.iter().map(|p| p.0), MARGIN_LEFT, options.width - MARGIN_RIGHT);
+    let y = Scale::new(points.iter().map(|p| p.1), options.height - MARGIN_BOTTOM, MARGIN_TOP);
+
+    let mut svg = header(options);
+    axes(&mut svg, &x, &y, &format!("f{}", x_axis + 1), &format!("f{}", y_axis + 1));
+
+    for point in reference_front {
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="1.5" fill="{}"/>"#,
+            x.map(point[x_axis]),
+            y.map(point[y_axis]),
+            REFERENCE_COLOR
+        );
+    }
+
+    let ranks = ranks(population, options.coloring);
+
+    // Draw worse fronts first so the best ones stay visible on top
+    let mut order = (0..population.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| std::cmp::Reverse(ranks[i]));
+
+    for i in order {
+        let individual = &population[i];
+        let (value_x, value_y) = (individual.objectives[x_axis], individual.objectives[y_axis]);
+
+        if !value_x.is_finite() || !value_y.is_finite() {
+            continue;
+        }
+
+        let color = color(individual.feasible, ranks[i], options.coloring);
+        let style = if individual.feasible || options.coloring == Coloring::Feasibility {
+            format!(r#"fill="{}""#, color)
+        } else {
+            format!(r#"fill="none" stroke="{}""#, color)
+        };
+
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="3" {}/>"#,
+            x.map(value_x),
+            y.map(value_y),
+            style
+        );
+    }
+
+    legend(&mut svg, options, &ranks, !reference_front.is_empty());
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render every objective of `population` as parallel coordinates, one
+/// vertical axis per objective scaled to the population's own range.
+pub fn parallel_coordinates<S>(population: &[&Individual<S>], options: &PlotOptions) -> String {
+    let objective_count = population.first().map_or(0, |i| i.objectives.len());
+    let spacing = (options.width - MARGIN_LEFT - MARGIN_RIGHT - LEGEND_WIDTH) / objective_count.saturating_sub(1).max(1) as f64;
+
+    let scales = (0..objective_count)
+        .map(|m| {
+            Scale::new(
+                population.iter().map(|individual| individual.objectives[m]),
+                options.height - MARGIN_BOTTOM,
+                MARGIN_TOP,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut svg = header(options);
+
+    for (m, scale) in scales.iter().enumerate() {
+        let position = MARGIN_LEFT + m as f64 * spacing;
+
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{0:.2}" y1="{1:.2}" x2="{0:.2}" y2="{2:.2}" stroke="black"/>"#,
+            position, MARGIN_TOP, options.height - MARGIN_BOTTOM
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" text-anchor="middle">f{}</text>"#,
+            position,
+            options.height - MARGIN_BOTTOM + 35.0,
+            m + 1
+        );
+
+        for (value, offset) in [(scale.min, 15.0), (scale.max, -6.0)] {
+            let anchor_y = if offset > 0.0 { options.height - MARGIN_BOTTOM } else { MARGIN_TOP };
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.2}" y="{:.2}" text-anchor="middle" font-size="10">{}</text>"#,
+                position,
+                anchor_y + offset,
+                label(value)
+            );
+        }
+    }
+
+    let ranks = ranks(population, options.coloring);
+
+    let mut order = (0..population.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| std::cmp::Reverse(ranks[i]));
+
+    for i in order {
+        let individual = &population[i];
+
+        if individual.objectives.iter().any(|value| !value.is_finite()) {
+            continue;
+        }
+
+        let points = individual
+            .objectives
+            .iter()
+            .zip(&scales)
+            .enumerate()
+            .map(|(m, (&value, scale))| format!("{:.2},{:.2}", MARGIN_LEFT + m as f64 * spacing, scale.map(value)))
+            .collect::<Vec<_>>();
+
+        let dash = if individual.feasible { "" } else { r#" stroke-dasharray="4 3""# };
+
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-opacity="0.5"{}/>"#,
+            points.join(" "),
+            color(individual.feasible, ranks[i], options.coloring),
+            dash
+        );
+    }
+
+    legend(&mut svg, options, &ranks, false);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Front index of every individual, or all zeros when coloring does not need them.
+fn ranks<S>(population: &[&Individual<S>], coloring: Coloring) -> Vec<usize> {
+    let mut ranks = vec![0; population.len()];
+
+    if coloring == Coloring::Rank {
+        let objectives = population
+            .iter()
+            .map(|individual| Individual {
+                solution: (),
+                objectives: individual.objectives.clone(),
+                feasible: individual.feasible,
+            })
+            .collect::<Vec<_>>();
+
+        for (rank, front) in non_dominated_sort(&objectives).into_iter().enumerate() {
+            for i in front {
+                ranks[i] = rank;
+            }
+        }
+    }
+
+    ranks
+}
+
+fn color(feasible: bool, rank: usize, coloring: Coloring) -> &'static str {
+    match coloring {
+        Coloring::Feasibility if feasible => FEASIBLE_COLOR,
+        Coloring::Feasibility => INFEASIBLE_COLOR,
+        Coloring::Rank => RANK_COLORS[rank.min(RANK_COLORS.len() - 1)],
+    }
+}
+
+fn header(options: &PlotOptions) -> String {
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="sans-serif" font-size="12">"#,
+        options.width, options.height
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    if !options.title.is_empty() {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.2}" y="24" text-anchor="middle" font-size="15">{}</text>"#,
+            options.width / 2.0,
+            escape(&options.title)
+        );
+    }
+
+    svg
+}
+
+fn axes(svg: &mut String, x: &Scale, y: &Scale, x_label: &str, y_label: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="none" stroke="black"/>"#,
+        x.from,
+        y.to,
+        x.to - x.from,
+        y.from - y.to
+    );
+
+    for tick in x.ticks(6) {
+        let position = x.map(tick);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{0:.2}" y1="{1:.2}" x2="{0:.2}" y2="{2:.2}" stroke="black"/><text x="{0:.2}" y="{3:.2}" text-anchor="middle">{4}</text>"#,
+            position,
+            y.from,
+            y.from + 5.0,
+            y.from + 18.0,
+            label(tick)
+        );
+    }
+
+    for tick in y.ticks(6) {
+        let position = y.map(tick);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{0:.2}" y1="{1:.2}" x2="{2:.2}" y2="{1:.2}" stroke="black"/><text x="{3:.2}" y="{4:.2}" text-anchor="end">{5}</text>"#,
+            x.from - 5.0,
+            position,
+            x.from,
+            x.from - 8.0,
+            position + 4.0,
+            label(tick)
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.2}" y="{:.2}" text-anchor="middle">{}</text>"#,
+        (x.from + x.to) / 2.0,
+        y.from + 38.0,
+        escape(x_label)
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text transform="translate({:.2} {:.2}) rotate(-90)" text-anchor="middle">{}</text>"#,
+        x.from - 50.0,
+        (y.from + y.to) / 2.0,
+        escape(y_label)
+    );
+}
+
+fn legend(svg: &mut String, options: &PlotOptions, ranks: &[usize], reference_front: bool) {
+    let mut entries = match options.coloring {
+        Coloring::Feasibility => vec![
+            ("feasible".to_string(), FEASIBLE_COLOR),
+            ("infeasible".to_string(), INFEASIBLE_COLOR),
+        ],
+        Coloring::Rank => {
+            let fronts = ranks.iter().max().map_or(0, |&worst| worst + 1).min(RANK_COLORS.len());
+            (0..fronts)
+                .map(|rank| {
+                    let name = if rank + 1 == RANK_COLORS.len() {
+                        format!("front {}+", rank + 1)
+                    } else {
+                        format!("front {}", rank + 1)
+                    };
+                    (name, RANK_COLORS[rank])
+                })
+                .collect()
+        }
+    };
+
+    if reference_front {
+        entries.push(("true front".to_string(), REFERENCE_COLOR));
+    }
+
+    let right = options.width - MARGIN_RIGHT - 10.0;
+
+    for (i, (name, color)) in entries.iter().enumerate() {
+        let y = MARGIN_TOP + 14.0 + i as f64 * 16.0;
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="4" fill="{}"/><text x="{:.2}" y="{:.2}" text-anchor="end">{}</text>"#,
+            right,
+            y - 4.0,
+            color,
+            right - 8.0,
+            y,
+            name
+        );
+    }
+}
+
+/// Tick label without trailing noise from floating-point steps.
+fn label(value: f64) -> String {
+    if value != 0.0 && !(1e-3..1e5).contains(&value.abs()) {
+        return format!("{:.2e}", value);
+    }
+
+    let rounded = format!("{:.4}", value);
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -1,4 +1,34 @@
 pub mod quadratic;
 pub mod thirty;
 pub mod rastrigin;
+pub mod functions;
+pub mod dtlz;
 pub mod series;
+pub mod knapsack;
+pub mod tsp;
+pub mod flowshop;
+pub mod external;
+
+/// Built-in problems by the name used on the command line, with a short description.
+pub const CATALOG: &[(&str, &str)] = &[
+    ("quadratic", "Problem 1.2: two quadratic objectives, two nonlinear constraints"),
+    ("zdt3", "Problem 1.3: ZDT3 with 30 variables and a disconnected front"),
+    ("rastrigin", "Single-objective Rastrigin function in 20 variables"),
+    ("sphere", "Single-objective sphere function in 30 variables"),
+    ("ackley", "Single-objective Ackley function in 30 variables"),
+    ("rosenbrock", "Single-objective Rosenbrock function in 30 variables"),
+    ("griewank", "Single-objective Griewank function in 30 variables"),
+    ("schwefel", "Single-objective Schwefel function in 30 variables"),
+    ("dtlz1", "DTLZ1 with 3 objectives and 7 variables: a linear front behind many local ones"),
+    ("dtlz2", "DTLZ2 with 3 objectives and 12 variables: a spherical front"),
+    ("series", "Problem 3.1: reliability-redundancy allocation of a 5-component series system"),
+    ("series_hikita", "Reliability-redundancy allocation of a 5-component series system (Hikita et al., 1992)"),
+    ("series_parallel", "Reliability-redundancy allocation of a 5-component series-parallel system"),
+    ("bridge", "Reliability-redundancy allocation of a 5-component bridge system"),
+    ("overspeed", "Reliability-redundancy allocation of a gas turbine overspeed protection system"),
+    ("variable_series", "Hikita's series system with any number of parallel subsystems per stage, up to 15"),
+    ("knapsack", "Bi-objective 0/1 knapsack with 100 items, as a bitstring"),
+    ("tsp", "Bi-objective travelling salesman through 50 cities, as a permutation"),
+    ("flow_shop", "Permutation flow-shop with 20 jobs on 5 machines: makespan and total flow time"),
+    ("external", "Black-box problem evaluated by an external program (see --program)"),
+];
@@ -0,0 +1,273 @@
+//! Scalable many-objective test problems of Deb, Thiele, Laumanns and Zitzler
+//! (2002), with their true Pareto fronts sampled from reference directions.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::sync::{Arc, OnceLock};
+
+use rand::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::directions::{das_dennis, divisions_for};
+use crate::evaluation::EvaluationError;
+use crate::genome::mixed::{Mixed, MixedSpace};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+/// Which DTLZ problem to minimize.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dtlz {
+    /// Linear front on the simplex `f_1 + ... + f_M = 0.5`, behind many local fronts
+    Dtlz1,
+    /// Spherical front on the positive part of the unit sphere
+    #[default]
+    Dtlz2,
+}
+
+impl Dtlz {
+    pub const ALL: [Dtlz; 2] = [Dtlz::Dtlz1, Dtlz::Dtlz2];
+
+    /// Name of the problem as a catalog problem
+    pub fn name(self) -> &'static str {
+        match self {
+            Dtlz::Dtlz1 => "dtlz1",
+            Dtlz::Dtlz2 => "dtlz2",
+        }
+    }
+
+    /// Number of variables beyond the `objectives - 1` position variables
+    /// suggested by the authors
+    pub fn distance_variables(self) -> usize {
+        match self {
+            Dtlz::Dtlz1 => 5,
+            Dtlz::Dtlz2 => 10,
+        }
+    }
+
+    /// The `objectives` objective values at `x`, whose last
+    /// `x.len() - objectives + 1` variables measure the distance to the front.
+    pub fn value(self, objectives: usize, x: &[f64]) -> Vec<f64> {
+        let (position, distance) = x.split_at(objectives - 1);
+
+        match self {
+            Dtlz::Dtlz1 => {
+                let g = 100.0
+                    * (distance.len() as f64
+                        + distance.iter().map(|x| (x - 0.5).powi(2) - (20.0 * PI * (x - 0.5)).cos()).sum::<f64>());
+
+                (0..objectives)
+                    .map(|i| {
+                        let kept = &position[..objectives - 1 - i];
+                        let last = if i > 0 { 1.0 - position[objectives - 1 - i] } else { 1.0 };
+                        0.5 * (1.0 + g) * kept.iter().product::<f64>() * last
+                    })
+                    .collect()
+            }
+            Dtlz::Dtlz2 => {
+                let g = distance.iter().map(|x| (x - 0.5).powi(2)).sum::<f64>();
+
+                (0..objectives)
+                    .map(|i| {
+                        let kept = &position[..objectives - 1 - i];
+                        let last = if i > 0 { (position[objectives - 1 - i] * FRAC_PI_2).sin() } else { 1.0 };
+                        (1.0 + g) * kept.iter().map(|x| (x * FRAC_PI_2).cos()).product::<f64>() * last
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// At least `samples` points on the true Pareto front in `objectives`
+    /// dimensions, one along each direction of the smallest Das–Dennis set
+    /// that has enough of them.
+    pub fn pareto_front(self, objectives: usize, samples: usize) -> Vec<Vec<f64>> {
+        let directions = das_dennis(objectives, divisions_for(objectives, samples));
+
+        match self {
+            Dtlz::Dtlz1 => directions.into_iter().map(|w| w.iter().map(|w| 0.5 * w).collect()).collect(),
+            Dtlz::Dtlz2 => directions
+                .into_iter()
+                .map(|w| {
+                    let norm = w.iter().map(|w| w * w).sum::<f64>().sqrt();
+                    w.iter().map(|w| w / norm).collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A DTLZ problem with its number of objectives and variables.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DtlzParams {
+    pub function: Dtlz,
+    pub objectives: usize,
+    pub variables: usize,
+}
+
+impl Default for DtlzParams {
+    fn default() -> Self {
+        Self::preset(Dtlz::default())
+    }
+}
+
+impl DtlzParams {
+    /// `function` with three objectives and the suggested number of variables
+    pub fn preset(function: Dtlz) -> Self {
+        let objectives = 3;
+
+        Self {
+            function,
+            objectives,
+            variables: objectives - 1 + function.distance_variables(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.objectives < 2 {
+            return Err(format!("{} needs at least 2 objectives, got {}", self.function.name(), self.objectives));
+        }
+        if self.variables < self.objectives {
+            return Err(format!(
+                "{} with {} objectives needs at least {} variables, got {}",
+                self.function.name(),
+                self.objectives,
+                self.objectives,
+                self.variables
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Every variable real within `[0, 1]`
+    pub fn space(&self) -> MixedSpace {
+        MixedSpace {
+            reals: vec![(0.0, 1.0); self.variables],
+            ..MixedSpace::default()
+        }
+    }
+
+    /// See [`Dtlz::pareto_front`]
+    pub fn pareto_front(&self, samples: usize) -> Vec<Vec<f64>> {
+        self.function.pareto_front(self.objectives, samples)
+    }
+}
+
+/// A DTLZ problem with its search space, built once and shared by its points.
+///
+/// Serialized as its [`DtlzParams`] only and rebuilt when read back.
+#[derive(Clone, Debug)]
+pub struct DtlzInstance {
+    pub params: DtlzParams,
+    pub space: MixedSpace,
+}
+
+impl DtlzInstance {
+    pub fn new(params: DtlzParams) -> Self {
+        let space = params.space();
+        Self { params, space }
+    }
+}
+
+impl Problem for Arc<DtlzInstance> {
+    type Solution = Point;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Point {
+        Point {
+            genome: self.space.random(rng),
+            instance: Arc::clone(self),
+        }
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.params.variables)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Point> {
+        Some(Point {
+            genome: self.space.genome_at(point),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<DtlzInstance> {
+    type Genome = Mixed;
+
+    type Params = DtlzParams;
+
+    fn name(&self) -> &str {
+        self.params.function.name()
+    }
+
+    fn params(&self) -> DtlzParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: DtlzParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(DtlzInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &Point) -> Mixed {
+        solution.genome.clone()
+    }
+
+    fn solution(&self, genome: Mixed) -> Result<Point, String> {
+        self.space.check(&genome)?;
+        Ok(Point { genome, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for DtlzInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DtlzInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DtlzParams::deserialize(deserializer).map(DtlzInstance::new)
+    }
+}
+
+/// A point in the search space of a DTLZ problem.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Point {
+    #[serde(flatten)]
+    pub genome: Mixed,
+    pub instance: Arc<DtlzInstance>,
+}
+
+impl Solution for Point {
+    type Data = Mixed;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.instance.space.mutate(&mut self.genome, rng);
+    }
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            genome: self.instance.space.crossover(&self.genome, &other.genome, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
+
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        let params = &self.instance.params;
+        Ok(params.function.value(params.objectives, &self.genome.reals))
+    }
+
+    fn feasible(&self) -> bool {
+        self.instance.space.contains(&self.genome)
+    }
+}
+
+impl Standalone for Point {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<DtlzInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(DtlzInstance::new(DtlzParams::default())))
+            .random(rng)
+    }
+}
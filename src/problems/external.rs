@@ -0,0 +1,374 @@
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use wait_timeout::ChildExt;
+
+use crate::evaluation::EvaluationError;
+use crate::experiment::ExternalConfig;
+use crate::genome::check_reals;
+use crate::solution::{Portable, Problem, Solution};
+
+/// How decision variables are written to, and results read from, the external program.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// `{"variables": [..]}` on stdin, `{"objectives": [..], "constraints": [..]}` on stdout.
+    #[default]
+    Json,
+    /// Whitespace-separated variables on one line of stdin. The first line of stdout
+    /// holds the objectives and an optional second line the constraint values.
+    Text,
+}
+
+/// Describes a black-box problem evaluated by running an executable once per candidate.
+///
+/// Constraint values follow the `g(x) <= 0` convention: a candidate is feasible
+/// when every reported constraint value is at most zero.
+#[derive(Clone, Debug)]
+pub struct ExternalSpec {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    /// Lower and upper bound of every decision variable
+    pub bounds: Vec<(f64, f64)>,
+    pub objectives: usize,
+    pub format: Format,
+    pub timeout: Duration,
+}
+
+impl ExternalSpec {
+    pub fn new(program: impl Into<PathBuf>, bounds: Vec<(f64, f64)>, objectives: usize) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            bounds,
+            objectives,
+            format: Format::Json,
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl Problem for Arc<ExternalSpec> {
+    type Solution = External;
+
+    /// Draw a uniformly random candidate within the bounds.
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> External {
+        let x = self.bounds.iter().map(|&(low, high)| rng.gen_range(low..=high)).collect();
+        External::new(x, Arc::clone(self))
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.bounds.len())
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<External> {
+        let x = self.bounds.iter().zip(point).map(|(&(low, high), u)| low + u * (high - low)).collect();
+        Some(External::new(x, Arc::clone(self)))
+    }
+}
+
+impl Portable for Arc<ExternalSpec> {
+    type Genome = ExternalGenome;
+
+    type Params = ExternalConfig;
+
+    fn name(&self) -> &str {
+        "external"
+    }
+
+    fn params(&self) -> ExternalConfig {
+        ExternalConfig {
+            program: self.program.clone(),
+            args: self.args.clone(),
+            bounds: self.bounds.clone(),
+            objectives: self.objectives,
+            format: self.format,
+            timeout_seconds: Some(self.timeout.as_secs_f64()),
+        }
+    }
+
+    fn from_params(params: ExternalConfig) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(params.spec()))
+    }
+
+    fn genome(&self, solution: &External) -> ExternalGenome {
+        ExternalGenome { x: solution.x.clone() }
+    }
+
+    fn solution(&self, genome: ExternalGenome) -> Result<External, String> {
+        check_reals(&genome.x, &self.bounds)?;
+        Ok(External::new(genome.x, Arc::clone(self)))
+    }
+}
+
+/// The variables of an [`External`] candidate without its spec.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExternalGenome {
+    pub x: Vec<f64>,
+}
+
+#[derive(Clone, Debug)]
+struct Outcome {
+    objectives: Vec<f64>,
+    constraints: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+struct JsonOutcome {
+    objectives: Vec<f64>,
+    #[serde(default)]
+    constraints: Vec<f64>,
+}
+
+/// A candidate for an [`ExternalSpec`] problem.
+///
+/// A successful run of the external program is cached and shared by `fitness`
+/// and `feasible`; failed runs are not, so retries start the program again.
+#[derive(Clone, Debug, Serialize)]
+pub struct External {
+    pub x: Vec<f64>,
+    #[serde(skip)]
+    spec: Arc<ExternalSpec>,
+    #[serde(skip)]
+    outcome: Arc<OnceLock<Outcome>>,
+}
+
+impl External {
+    pub fn new(x: Vec<f64>, spec: Arc<ExternalSpec>) -> Self {
+        Self { x, spec, outcome: Arc::default() }
+    }
+
+    pub fn spec(&self) -> &Arc<ExternalSpec> {
+        &self.spec
+    }
+
+    fn outcome(&self) -> Result<&Outcome, EvaluationError> {
+        if let Some(outcome) = self.outcome.get() {
+            return Ok(outcome);
+        }
+
+        let outcome = self.run()?;
+        Ok(self.outcome.get_or_init(|| outcome))
+    }
+
+    fn run(&self) -> Result<Outcome, EvaluationError> {
+        let spec = &self.spec;
+
+        let input = match spec.format {
+            Format::Json => serde_json::json!({ "variables": self.x }).to_string(),
+            Format::Text => self.x.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" "),
+        };
+
+        let mut child = Command::new(&spec.program)
+            .args(&spec.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| EvaluationError::Failed(format!("could not start {}: {}", spec.program.display(), e)))?;
+
+        // Read both pipes on their own threads so a chatty program cannot block on a full pipe
+        let stdout = read_in_background(child.stdout.take().expect("stdout is piped"));
+        let stderr = read_in_background(child.stderr.take().expect("stderr is piped"));
+
+        // The input goes through a pipe too, so a program that never reads it cannot
+        // block the write past the timeout; the pipe closes when the writer finishes
+        let deadline = Instant::now() + spec.timeout;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        thread::spawn(move || {
+            // A program that exits without reading its input is judged by its output alone
+            let _ = writeln!(stdin, "{}", input);
+        });
+
+        let timed_out = |mut child: std::process::Child| {
+            let _ = child.kill();
+            let _ = child.wait();
+            Err(EvaluationError::Timeout(spec.timeout))
+        };
+
+        let status = match child.wait_timeout(spec.timeout) {
+            Ok(Some(status)) => status,
+            Ok(None) => return timed_out(child),
+            Err(e) => return Err(EvaluationError::Failed(e.to_string())),
+        };
+
+        // Processes the program started may still hold the pipes open after it exits,
+        // so the output is only waited for until the deadline
+        let remaining = || deadline.saturating_duration_since(Instant::now());
+        let (stdout, stderr) = match (stdout.recv_timeout(remaining()), stderr.recv_timeout(remaining())) {
+            (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+            (Err(RecvTimeoutError::Timeout), _) | (_, Err(RecvTimeoutError::Timeout)) => return timed_out(child),
+            _ => return Err(EvaluationError::Failed("output reader stopped".to_string())),
+        };
+        let stdout = stdout.map_err(|e| EvaluationError::Failed(format!("could not read output: {}", e)))?;
+        let stderr = stderr.unwrap_or_default();
+
+        if !status.success() {
+            return Err(EvaluationError::Failed(format!("{} ({})", status, stderr.trim())));
+        }
+
+        let outcome = match spec.format {
+            Format::Json => parse_json(&stdout)?,
+            Format::Text => parse_text(&stdout)?,
+        };
+
+        if outcome.objectives.len() != spec.objectives {
+            return Err(EvaluationError::Failed(format!(
+                "expected {} objectives, got {}",
+                spec.objectives,
+                outcome.objectives.len()
+            )));
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Read `pipe` to its end on its own thread, handing over the text once it closes.
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> Receiver<io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = String::new();
+        let _ = sender.send(pipe.read_to_string(&mut buffer).map(|_| buffer));
+    });
+    receiver
+}
+
+fn parse_json(output: &str) -> Result<Outcome, EvaluationError> {
+    let parsed: JsonOutcome = serde_json::from_str(output)
+        .map_err(|e| EvaluationError::Failed(format!("malformed output: {}", e)))?;
+
+    Ok(Outcome {
+        objectives: parsed.objectives,
+        constraints: parsed.constraints,
+    })
+}
+
+fn parse_text(output: &str) -> Result<Outcome, EvaluationError> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+
+    let parse_line = |line: &str| {
+        line.split_whitespace()
+            .map(|token| {
+                token
+                    .parse::<f64>()
+                    .map_err(|e| EvaluationError::Failed(format!("malformed output {:?}: {}", token, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let objectives = parse_line(lines.next().unwrap_or_default())?;
+    let constraints = match lines.next() {
+        Some(line) => parse_line(line)?,
+        None => Vec::new(),
+    };
+
+    Ok(Outcome { objectives, constraints })
+}
+
+impl Solution for External {
+    type Data = Vec<f64>;
+
+    /// Perturb a random selection of variables by up to 10% of their range
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let n = self.x.len();
+        if n == 0 {
+            return;
+        }
+
+        let num_mutations = rng.gen_range(1..=(n / 4).max(1));
+
+        for _ in 0..num_mutations {
+            let idx = rng.gen_range(0..n);
+            let (low, high) = self.spec.bounds[idx];
+            let strength = 0.1 * (high - low);
+            let delta = rng.gen_range(-strength..=strength);
+            self.x[idx] = (self.x[idx] + delta).clamp(low, high);
+        }
+
+        // The cached result belongs to the unmutated variables
+        self.outcome = Arc::default();
+    }
+
+    /// Simulated binary crossover (SBX)
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let eta = 2.0;
+
+        let x = self
+            .x
+            .iter()
+            .zip(&other.x)
+            .zip(&self.spec.bounds)
+            .map(|((&a, &b), &(low, high))| {
+                let u = rng.gen::<f64>();
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(1.0 / (eta + 1.0))
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (eta + 1.0))
+                };
+
+                (0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)).clamp(low, high)
+            })
+            .collect();
+
+        Self::new(x, Arc::clone(&self.spec))
+    }
+
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        self.outcome().map(|outcome| outcome.objectives.clone())
+    }
+
+    fn feasible(&self) -> bool {
+        match self.outcome() {
+            Ok(outcome) => outcome.constraints.iter().all(|&g| g <= 0.0),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str, timeout: Duration) -> External {
+        let mut spec = ExternalSpec::new("sh", vec![(0.0, 1.0)], 1);
+        spec.args = vec!["-c".to_string(), script.to_string()];
+        spec.timeout = timeout;
+        External::new(vec![0.5], Arc::new(spec))
+    }
+
+    #[test]
+    fn reads_objectives_and_constraints() {
+        let script = r#"cat > /dev/null; echo '{"objectives": [2.5], "constraints": [-1]}'"#;
+        let candidate = shell(script, Duration::from_secs(10));
+
+        assert_eq!(candidate.fitness(), Ok(vec![2.5]));
+        assert!(candidate.feasible());
+    }
+
+    #[test]
+    fn times_out_on_a_slow_program() {
+        let candidate = shell("sleep 10", Duration::from_millis(200));
+
+        let started = Instant::now();
+        assert_eq!(candidate.fitness(), Err(EvaluationError::Timeout(Duration::from_millis(200))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn times_out_when_a_child_process_holds_the_output_open() {
+        let candidate = shell(r#"sleep 10 & echo '{"objectives": [1]}'"#, Duration::from_millis(200));
+
+        let started = Instant::now();
+        assert_eq!(candidate.fitness(), Err(EvaluationError::Timeout(Duration::from_millis(200))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,206 @@
+//! Bi-objective permutation flow-shop: every job passes through the machines
+//! in the same order, and the jobs are processed in the same sequence on every
+//! machine. Both the makespan and the total flow time are minimized.
+
+use std::sync::{Arc, OnceLock};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::evaluation::EvaluationError;
+use crate::genome::permutation::{Permutation, PermutationCrossover, PermutationMutation};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+/// Size of a generated instance and the seed it is generated from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlowShopParams {
+    pub jobs: usize,
+    pub machines: usize,
+    /// Seed for the processing times; the same seed always gives the same instance
+    pub instance_seed: u64,
+    pub crossover: PermutationCrossover,
+    pub mutation: PermutationMutation,
+}
+
+impl Default for FlowShopParams {
+    fn default() -> Self {
+        Self {
+            jobs: 20,
+            machines: 5,
+            instance_seed: 1,
+            crossover: PermutationCrossover::default(),
+            mutation: PermutationMutation::Insert,
+        }
+    }
+}
+
+impl FlowShopParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.jobs == 0 {
+            return Err("flow-shop needs at least one job".to_string());
+        }
+
+        if self.machines == 0 {
+            return Err("flow-shop needs at least one machine".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Processing time of every job on every machine, uniform integers in
+/// `[1, 99]` as in Taillard's benchmarks.
+///
+/// Serialized as its [`FlowShopParams`] only and regenerated when read back.
+#[derive(Clone, Debug)]
+pub struct FlowShopInstance {
+    pub params: FlowShopParams,
+    /// `times[j][m]` is the processing time of job `j` on machine `m`
+    pub times: Vec<Vec<f64>>,
+}
+
+impl FlowShopInstance {
+    pub fn new(params: FlowShopParams) -> Self {
+        let mut rng = StdRng::seed_from_u64(params.instance_seed);
+        let times = (0..params.jobs)
+            .map(|_| (0..params.machines).map(|_| rng.gen_range(1..=99) as f64).collect())
+            .collect();
+
+        Self { params, times }
+    }
+}
+
+impl Problem for Arc<FlowShopInstance> {
+    type Solution = FlowShop;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> FlowShop {
+        FlowShop {
+            sequence: Permutation::random(self.params.jobs, rng),
+            instance: Arc::clone(self),
+        }
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.params.jobs)
+    }
+
+    /// The sequence processing the jobs in increasing order of their coordinates
+    fn solution_at(&self, point: &[f64]) -> Option<FlowShop> {
+        Some(FlowShop {
+            sequence: Permutation::from_keys(point),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<FlowShopInstance> {
+    type Genome = FlowShopGenome;
+
+    type Params = FlowShopParams;
+
+    fn name(&self) -> &str {
+        "flow_shop"
+    }
+
+    fn params(&self) -> FlowShopParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: FlowShopParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(FlowShopInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &FlowShop) -> FlowShopGenome {
+        FlowShopGenome { sequence: solution.sequence.clone() }
+    }
+
+    fn solution(&self, genome: FlowShopGenome) -> Result<FlowShop, String> {
+        genome.sequence.check(self.params.jobs)?;
+        Ok(FlowShop { sequence: genome.sequence, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for FlowShopInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FlowShopInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FlowShopParams::deserialize(deserializer).map(FlowShopInstance::new)
+    }
+}
+
+/// The variables of a [`FlowShop`] without its instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlowShopGenome {
+    pub sequence: Permutation,
+}
+
+/// The order the jobs are processed in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlowShop {
+    pub sequence: Permutation,
+    pub instance: Arc<FlowShopInstance>,
+}
+
+impl FlowShop {
+    /// Time every job leaves the last machine, in sequence order
+    pub fn completion_times(&self) -> Vec<f64> {
+        // Time each machine becomes free
+        let mut free = vec![0.0; self.instance.params.machines];
+
+        self.sequence
+            .0
+            .iter()
+            .map(|&job| {
+                let mut done: f64 = 0.0;
+                for (machine, time) in free.iter_mut().zip(&self.instance.times[job]) {
+                    done = done.max(*machine) + time;
+                    *machine = done;
+                }
+                done
+            })
+            .collect()
+    }
+}
+
+impl Solution for FlowShop {
+    type Data = Permutation;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.sequence.mutate(self.instance.params.mutation, rng);
+    }
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            sequence: self.sequence.crossover(&other.sequence, self.instance.params.crossover, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
+
+    /// Makespan and total flow time
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        let completion = self.completion_times();
+        let makespan = completion.last().copied().unwrap_or(0.0);
+
+        Ok(vec![makespan, completion.iter().sum()])
+    }
+
+    fn feasible(&self) -> bool {
+        self.sequence.len() == self.instance.params.jobs && self.sequence.is_valid()
+    }
+}
+
+impl Standalone for FlowShop {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<FlowShopInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(FlowShopInstance::new(FlowShopParams::default())))
+            .random(rng)
+    }
+}
@@ -0,0 +1,234 @@
+//! Classic single-objective test functions of any dimension, all with their
+//! global minimum of 0 (Schwefel's up to rounding).
+
+use std::f64::consts::{E, PI};
+use std::sync::{Arc, OnceLock};
+
+use rand::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::evaluation::EvaluationError;
+use crate::genome::mixed::{Mixed, MixedSpace};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+/// Which function to minimize.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Function {
+    /// Sum of squares: unimodal and separable
+    #[default]
+    Sphere,
+    /// Nearly flat outer region around a deep central funnel, full of local minima
+    Ackley,
+    /// A narrow curved valley with its minimum at `(1, ..., 1)`
+    Rosenbrock,
+    /// Many regularly spaced local minima, their depth shrinking with distance
+    Griewank,
+    /// Deceptive: the second best minimum lies far from the global one at `(420.97, ..., 420.97)`
+    Schwefel,
+}
+
+impl Function {
+    pub const ALL: [Function; 5] =
+        [Function::Sphere, Function::Ackley, Function::Rosenbrock, Function::Griewank, Function::Schwefel];
+
+    /// Name of the function as a catalog problem
+    pub fn name(self) -> &'static str {
+        match self {
+            Function::Sphere => "sphere",
+            Function::Ackley => "ackley",
+            Function::Rosenbrock => "rosenbrock",
+            Function::Griewank => "griewank",
+            Function::Schwefel => "schwefel",
+        }
+    }
+
+    /// The usual search range of every variable
+    pub fn bounds(self) -> (f64, f64) {
+        match self {
+            Function::Sphere => (-5.12, 5.12),
+            Function::Ackley => (-32.768, 32.768),
+            Function::Rosenbrock => (-5.0, 10.0),
+            Function::Griewank => (-600.0, 600.0),
+            Function::Schwefel => (-500.0, 500.0),
+        }
+    }
+
+    pub fn value(self, x: &[f64]) -> f64 {
+        let n = x.len() as f64;
+
+        match self {
+            Function::Sphere => x.iter().map(|x| x * x).sum(),
+            Function::Ackley => {
+                let squares = x.iter().map(|x| x * x).sum::<f64>() / n;
+                let cosines = x.iter().map(|x| (2.0 * PI * x).cos()).sum::<f64>() / n;
+                -20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + E
+            }
+            Function::Rosenbrock => x
+                .windows(2)
+                .map(|pair| 100.0 * (pair[1] - pair[0] * pair[0]).powi(2) + (1.0 - pair[0]).powi(2))
+                .sum(),
+            Function::Griewank => {
+                let squares = x.iter().map(|x| x * x).sum::<f64>() / 4000.0;
+                let cosines = x.iter().enumerate().map(|(i, x)| (x / ((i + 1) as f64).sqrt()).cos()).product::<f64>();
+                1.0 + squares - cosines
+            }
+            Function::Schwefel => 418.9829 * n - x.iter().map(|x| x * x.abs().sqrt().sin()).sum::<f64>(),
+        }
+    }
+}
+
+/// A test function and the number of variables it is minimized over.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FunctionParams {
+    pub function: Function,
+    pub dimension: usize,
+}
+
+impl Default for FunctionParams {
+    fn default() -> Self {
+        Self {
+            function: Function::default(),
+            dimension: 30,
+        }
+    }
+}
+
+impl FunctionParams {
+    pub fn validate(&self) -> Result<(), String> {
+        let minimum = if self.function == Function::Rosenbrock { 2 } else { 1 };
+
+        if self.dimension < minimum {
+            return Err(format!("{} needs at least {} variables, got {}", self.function.name(), minimum, self.dimension));
+        }
+
+        Ok(())
+    }
+
+    /// Every variable real within the bounds of the function
+    pub fn space(&self) -> MixedSpace {
+        MixedSpace {
+            reals: vec![self.function.bounds(); self.dimension],
+            ..MixedSpace::default()
+        }
+    }
+}
+
+/// A test function with its search space, built once and shared by its points.
+///
+/// Serialized as its [`FunctionParams`] only and rebuilt when read back.
+#[derive(Clone, Debug)]
+pub struct FunctionInstance {
+    pub params: FunctionParams,
+    pub space: MixedSpace,
+}
+
+impl FunctionInstance {
+    pub fn new(params: FunctionParams) -> Self {
+        let space = params.space();
+        Self { params, space }
+    }
+}
+
+impl Problem for Arc<FunctionInstance> {
+    type Solution = Point;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Point {
+        Point {
+            genome: self.space.random(rng),
+            instance: Arc::clone(self),
+        }
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.params.dimension)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Point> {
+        Some(Point {
+            genome: self.space.genome_at(point),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<FunctionInstance> {
+    type Genome = Mixed;
+
+    type Params = FunctionParams;
+
+    fn name(&self) -> &str {
+        self.params.function.name()
+    }
+
+    fn params(&self) -> FunctionParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: FunctionParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(FunctionInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &Point) -> Mixed {
+        solution.genome.clone()
+    }
+
+    fn solution(&self, genome: Mixed) -> Result<Point, String> {
+        self.space.check(&genome)?;
+        Ok(Point { genome, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for FunctionInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FunctionInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FunctionParams::deserialize(deserializer).map(FunctionInstance::new)
+    }
+}
+
+/// A point in the search space of a test function.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Point {
+    #[serde(flatten)]
+    pub genome: Mixed,
+    pub instance: Arc<FunctionInstance>,
+}
+
+impl Solution for Point {
+    type Data = Mixed;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.instance.space.mutate(&mut self.genome, rng);
+    }
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            genome: self.instance.space.crossover(&self.genome, &other.genome, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
+
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        Ok(vec![self.instance.params.function.value(&self.genome.reals)])
+    }
+
+    fn feasible(&self) -> bool {
+        self.instance.space.contains(&self.genome)
+    }
+}
+
+impl Standalone for Point {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<FunctionInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(FunctionInstance::new(FunctionParams::default())))
+            .random(rng)
+    }
+}
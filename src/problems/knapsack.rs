@@ -0,0 +1,240 @@
+//! Multi-objective 0/1 knapsack (Zitzler and Thiele, 1999): choose items to
+//! put into every knapsack at once, maximizing the profit in each knapsack
+//! without exceeding any capacity.
+
+use std::sync::{Arc, OnceLock};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::evaluation::EvaluationError;
+use crate::genome::bits::{BitCrossover, Bits};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+/// Size of a generated instance and the seed it is generated from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KnapsackParams {
+    pub items: usize,
+    /// Number of knapsacks, and so of objectives
+    pub knapsacks: usize,
+    /// Seed for the profits and weights; the same seed always gives the same instance
+    pub instance_seed: u64,
+    pub crossover: BitCrossover,
+}
+
+impl Default for KnapsackParams {
+    fn default() -> Self {
+        Self {
+            items: 100,
+            knapsacks: 2,
+            instance_seed: 1,
+            crossover: BitCrossover::default(),
+        }
+    }
+}
+
+impl KnapsackParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.items == 0 {
+            return Err("knapsack problem needs at least one item".to_string());
+        }
+
+        if self.knapsacks == 0 {
+            return Err("knapsack problem needs at least one knapsack".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Profits and weights of every item in every knapsack, uniform integers in
+/// `[10, 100]`, with each capacity half the total weight of its knapsack.
+///
+/// Overfull selections are repaired greedily, unpacking items in increasing
+/// order of their best profit-to-weight ratio until every knapsack fits.
+///
+/// Serialized as its [`KnapsackParams`] only and regenerated when read back.
+#[derive(Clone, Debug)]
+pub struct KnapsackInstance {
+    pub params: KnapsackParams,
+    /// `profits[k][j]` is the profit of item `j` in knapsack `k`
+    pub profits: Vec<Vec<f64>>,
+    /// `weights[k][j]` is the weight of item `j` in knapsack `k`
+    pub weights: Vec<Vec<f64>>,
+    pub capacities: Vec<f64>,
+    /// Items in the order repair unpacks them
+    removal_order: Vec<usize>,
+}
+
+impl KnapsackInstance {
+    pub fn new(params: KnapsackParams) -> Self {
+        let mut rng = StdRng::seed_from_u64(params.instance_seed);
+        let mut table = || {
+            (0..params.knapsacks)
+                .map(|_| (0..params.items).map(|_| rng.gen_range(10..=100) as f64).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        let weights = table();
+        let profits = table();
+        let capacities = weights.iter().map(|row| row.iter().sum::<f64>() / 2.0).collect();
+
+        let ratio = |j: usize| {
+            (0..params.knapsacks)
+                .map(|k| profits[k][j] / weights[k][j])
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+        let mut removal_order = (0..params.items).collect::<Vec<_>>();
+        removal_order.sort_by(|&a, &b| ratio(a).total_cmp(&ratio(b)));
+
+        Self { params, profits, weights, capacities, removal_order }
+    }
+}
+
+impl Problem for Arc<KnapsackInstance> {
+    type Solution = Knapsack;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Knapsack {
+        let mut knapsack = Knapsack {
+            items: Bits::random(self.params.items, rng),
+            instance: Arc::clone(self),
+        };
+        knapsack.repair();
+        knapsack
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.params.items)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Knapsack> {
+        let mut knapsack = Knapsack {
+            items: Bits::from_unit(point),
+            instance: Arc::clone(self),
+        };
+        knapsack.repair();
+        Some(knapsack)
+    }
+}
+
+impl Portable for Arc<KnapsackInstance> {
+    type Genome = KnapsackGenome;
+
+    type Params = KnapsackParams;
+
+    fn name(&self) -> &str {
+        "knapsack"
+    }
+
+    fn params(&self) -> KnapsackParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: KnapsackParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(KnapsackInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &Knapsack) -> KnapsackGenome {
+        KnapsackGenome { items: solution.items.clone() }
+    }
+
+    fn solution(&self, genome: KnapsackGenome) -> Result<Knapsack, String> {
+        if genome.items.len() != self.params.items {
+            return Err(format!("expected {} items, got {}", self.params.items, genome.items.len()));
+        }
+        Ok(Knapsack { items: genome.items, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for KnapsackInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KnapsackInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        KnapsackParams::deserialize(deserializer).map(KnapsackInstance::new)
+    }
+}
+
+/// The variables of a [`Knapsack`] without its instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KnapsackGenome {
+    pub items: Bits,
+}
+
+/// A selection of items, set bits meaning packed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Knapsack {
+    pub items: Bits,
+    pub instance: Arc<KnapsackInstance>,
+}
+
+impl Knapsack {
+    /// Sum of `table` over the packed items, per knapsack
+    fn totals(&self, table: &[Vec<f64>]) -> Vec<f64> {
+        table.iter().map(|row| self.items.ones().map(|j| row[j]).sum()).collect()
+    }
+
+    /// Unpack the least profitable items until every knapsack is within its capacity
+    fn repair(&mut self) {
+        let instance = Arc::clone(&self.instance);
+        let mut load = self.totals(&instance.weights);
+
+        for &j in &instance.removal_order {
+            if load.iter().zip(&instance.capacities).all(|(weight, capacity)| weight <= capacity) {
+                break;
+            }
+
+            if self.items.0[j] {
+                self.items.0[j] = false;
+                for (k, weight) in load.iter_mut().enumerate() {
+                    *weight -= instance.weights[k][j];
+                }
+            }
+        }
+    }
+}
+
+impl Solution for Knapsack {
+    type Data = Bits;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.items.flip(rng);
+        self.repair();
+    }
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let mut child = Self {
+            items: self.items.crossover(&other.items, self.instance.params.crossover, rng),
+            instance: Arc::clone(&self.instance),
+        };
+        child.repair();
+        child
+    }
+
+    /// Profit in every knapsack, negated for minimization
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        Ok(self.totals(&self.instance.profits).into_iter().map(|profit| -profit).collect())
+    }
+
+    fn feasible(&self) -> bool {
+        self.totals(&self.instance.weights)
+            .iter()
+            .zip(&self.instance.capacities)
+            .all(|(weight, capacity)| weight <= capacity)
+    }
+}
+
+impl Standalone for Knapsack {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<KnapsackInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(KnapsackInstance::new(KnapsackParams::default())))
+            .random(rng)
+    }
+}
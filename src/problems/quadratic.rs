@@ -1,26 +1,69 @@
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::solution::Solution;
+use crate::evaluation::EvaluationError;
+use crate::genome::check_reals;
+use crate::solution::{Portable, Problem, Solution, Standalone};
 
-#[derive(Clone, Debug)]
-pub(crate) struct Quadratic {
-    pub(crate) x1: f64,
-    pub(crate) x2: f64,
+/// Problem 1.2, which has no parameters, as a [`Problem`] instance.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuadraticProblem;
+
+impl Problem for QuadraticProblem {
+    type Solution = Quadratic;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Quadratic {
+        Quadratic::random(rng)
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Quadratic> {
+        Some(Quadratic {
+            x1: 5.0 * point[0],
+            x2: 3.0 * point[1],
+        })
+    }
 }
 
-impl Solution for Quadratic {
-    type Data = (f64, f64);
+impl Portable for QuadraticProblem {
+    type Genome = Quadratic;
 
-    fn random() -> Self {
-        let mut rng = thread_rng();
-        let x1 = rng.gen_range(0.0..=5.0);
-        let x2 = rng.gen_range(0.0..=3.0);
-        Self { x1, x2 }
+    type Params = ();
+
+    fn name(&self) -> &str {
+        "quadratic"
+    }
+
+    fn params(&self) {}
+
+    fn from_params(_params: ()) -> Result<Self, String> {
+        Ok(QuadraticProblem)
+    }
+
+    fn genome(&self, solution: &Quadratic) -> Quadratic {
+        solution.clone()
+    }
+
+    fn solution(&self, genome: Quadratic) -> Result<Quadratic, String> {
+        check_reals(&[genome.x1, genome.x2], &[(0.0, 5.0), (0.0, 3.0)])?;
+        Ok(genome)
     }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Quadratic {
+    pub x1: f64,
+    pub x2: f64,
+}
+
+impl Solution for Quadratic {
+    type Data = (f64, f64);
 
     /// Add/subtract small random values to/from x_1 and x_2
-    fn mutate(&mut self) {
-        let mut rng = thread_rng();
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         let mutation_strength = 0.1;
 
         let mut x1 = self.x1 + rng.gen_range(-mutation_strength..=mutation_strength);
@@ -35,8 +78,7 @@ impl Solution for Quadratic {
     }
 
     /// Blend crossover (BLX-α)
-    fn crossover(&self, other: &Self) -> Self {
-        let mut rng = thread_rng();
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
         let alpha = 0.5;
 
         let gamma1 = (1.0 + 2.0 * alpha) * rng.gen::<f64>() - alpha;
@@ -51,31 +93,10 @@ impl Solution for Quadratic {
         Self { x1, x2 }
     }
 
-    fn fitness(&self) -> Vec<f64> {
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
         let f1 = 4.0 * self.x1.powi(2) + 4.0 * self.x2.powi(2);
         let f2 = (self.x1 - 5.0).powi(2) + (self.x2 - 5.0).powi(2);
-        vec![f1, f2]
-    }
-
-    fn dominates(&self, other: &Self) -> bool {
-        let self_fitness = self.fitness();
-        let other_fitness = other.fitness();
-
-        let mut better_in_any = false;
-
-        for (self_value, other_value) in self_fitness.iter().zip(other_fitness.iter()) {
-            // If self is worse in any objective, it does not dominate
-            if self_value > other_value {
-                return false;
-            }
-
-            // All objectives should be minimized
-            if self_value < other_value {
-                better_in_any = true;
-            }
-        }
-
-        better_in_any
+        Ok(vec![f1, f2])
     }
 
     fn feasible(&self) -> bool {
@@ -83,4 +104,12 @@ impl Solution for Quadratic {
         let c2 = (self.x1 - 8.0).powi(2) + (self.x2 + 3.0).powi(2) >= 7.7 - 1e-6;
         c1 && c2
     }
+}
+
+impl Standalone for Quadratic {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let x1 = rng.gen_range(0.0..=5.0);
+        let x2 = rng.gen_range(0.0..=3.0);
+        Self { x1, x2 }
+    }
 }
\ No newline at end of file
@@ -1,15 +1,87 @@
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
-use crate::solution::Solution;
+use crate::evaluation::EvaluationError;
+use crate::genome::check_reals;
+use crate::solution::{Portable, Problem, Solution, Standalone};
 
-#[derive(Clone, Debug)]
-pub(crate) struct Rastrigin {
-    pub(crate) data: Vec<f64>, // Decision variables x_i, where i = 1..n
+/// Size of a Rastrigin problem.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RastriginParams {
+    /// Number of decision variables
+    pub dimension: usize,
+}
+
+impl Default for RastriginParams {
+    fn default() -> Self {
+        Self { dimension: 20 }
+    }
+}
+
+impl RastriginParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.dimension == 0 {
+            return Err("rastrigin needs at least one variable".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Problem for RastriginParams {
+    type Solution = Rastrigin;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Rastrigin {
+        Rastrigin::random_n(self.dimension, rng)
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.dimension)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Rastrigin> {
+        Some(Rastrigin {
+            data: point.iter().map(|u| -5.12 + u * 10.24).collect(),
+        })
+    }
+}
+
+impl Portable for RastriginParams {
+    type Genome = Rastrigin;
+
+    type Params = RastriginParams;
+
+    fn name(&self) -> &str {
+        "rastrigin"
+    }
+
+    fn params(&self) -> RastriginParams {
+        self.clone()
+    }
+
+    fn from_params(params: RastriginParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn genome(&self, solution: &Rastrigin) -> Rastrigin {
+        solution.clone()
+    }
+
+    fn solution(&self, genome: Rastrigin) -> Result<Rastrigin, String> {
+        check_reals(&genome.data, &vec![(-5.12, 5.12); self.dimension])?;
+        Ok(genome)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Rastrigin {
+    pub data: Vec<f64>, // Decision variables x_i, where i = 1..n
 }
 
 impl Rastrigin {
-    fn random_n(n: usize) -> Self {
-        let mut rng = thread_rng();
+    pub fn random_n<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
         let data = (0..n).map(|_| rng.gen_range(-5.12..=5.12)).collect();
         Self { data }
     }
@@ -18,22 +90,13 @@ impl Rastrigin {
 impl Solution for Rastrigin {
     type Data = Vec<f64>;
 
-    /// Initialize a new random solution
-    fn random() -> Self {
-        let mut rng = thread_rng();
-        let n = 20;
-        let data = (0..n).map(|_| rng.gen_range(-5.12..=5.12)).collect();
-        Self { data }
-    }
-    
     /// Add/subtract small random values to the decision variables
-    fn mutate(&mut self) {
-        let mut rng = thread_rng();
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         let mutation_strength = 0.2;
         let n = self.data.len();
 
         // Decide how many variables should be mutated, up to a maximum of 25%
-        let num_mutations = rng.gen_range(1..=n / 4);
+        let num_mutations = rng.gen_range(1..=(n / 4).max(1));
 
         for _ in 0..num_mutations {
             let idx = rng.gen_range(0..n);
@@ -43,25 +106,24 @@ impl Solution for Rastrigin {
     }
 
     /// Blend crossover (BLX-α)
-    fn crossover(&self, other: &Self) -> Self {
-        let mut rng = thread_rng();
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
         let n = self.data.len();
         let alpha = 0.5;
         let mut blended = vec![0.0; n];
 
-        for i in 0..n {
+        for (i, value) in blended.iter_mut().enumerate() {
             let c_min = self.data[i].min(other.data[i]);
             let c_max = self.data[i].max(other.data[i]);
             let range = c_max - c_min;
             let lower = c_min - alpha * range;
             let upper = c_max + alpha * range;
-            blended[i] = rng.gen_range(lower..=upper).clamp(-5.12, 5.12);
+            *value = rng.gen_range(lower..=upper).clamp(-5.12, 5.12);
         }
 
         Self { data: blended }
     }
     
-    fn fitness(&self) -> Vec<f64> {
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
         let n = self.data.len() as f64;
         let sum: f64 = self
             .data
@@ -69,16 +131,17 @@ impl Solution for Rastrigin {
             .map(|&x| x.powi(2) - 10.0 * (2.0 * PI * x).cos())
             .sum();
         let f = 10.0 * n + sum;
-        vec![f]
-    }
-    
-    fn dominates(&self, other: &Self) -> bool {
-        let self_fitness = self.fitness()[0];
-        let other_fitness = other.fitness()[0];
-        self_fitness < other_fitness
+        Ok(vec![f])
     }
     
     fn feasible(&self) -> bool {
         self.data.iter().all(|&x| (-5.12..=5.12).contains(&x))
     }
 }
+
+impl Standalone for Rastrigin {
+    /// Initialize a new random solution
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::random_n(20, rng)
+    }
+}
@@ -1,161 +1,746 @@
-use crate::solution::Solution;
+//! Reliability-redundancy allocation (Hikita et al., 1992): choose the
+//! reliability and the number of redundant units of every component of a
+//! system, maximizing the system reliability at the least cost within volume,
+//! cost and weight budgets.
+
+use crate::evaluation::EvaluationError;
+use crate::experiment::{ExperimentError, FileFormat};
+use crate::genome::mixed::{Mixed, MixedSpace};
+use crate::genome::variable::{Variable, VariableCrossover, VariableSpace};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
 use rand::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, Debug)]
-pub(crate) struct Series {
-    pub(crate) r: Vec<f64>,  // Reliabilities – continuous
-    pub(crate) n: Vec<u32>,  // Redundancies - discrete
-    pub(crate) m: usize,     // Number of components
+/// How the components are connected, from the standard RRAP test problems.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesSystem {
+    /// Every component must work, with any number of components
+    #[default]
+    Series,
+    /// Components 1 and 2 in series, in parallel with 3 and 4 in parallel, in series with 5
+    SeriesParallel,
+    /// Five components in a bridge, 5 linking the two paths 1-2 and 3-4
+    Bridge,
+    /// Overspeed protection of a gas turbine (Dhingra, 1992): four components in series
+    Overspeed,
 }
 
-fn random(m: usize) -> Series {
-    let mut rng = thread_rng();
+impl SeriesSystem {
+    pub const ALL: [SeriesSystem; 4] =
+        [SeriesSystem::Series, SeriesSystem::SeriesParallel, SeriesSystem::Bridge, SeriesSystem::Overspeed];
 
-    // Initialize reliabilities r_i between 0.01 and 1.0 to avoid ln(0)
-    let r = (0..m).map(|_| rng.gen_range(0.01..=1.0)).collect();
+    /// Name of the system as a catalog problem
+    pub fn name(self) -> &'static str {
+        match self {
+            SeriesSystem::Series => "series",
+            SeriesSystem::SeriesParallel => "series_parallel",
+            SeriesSystem::Bridge => "bridge",
+            SeriesSystem::Overspeed => "overspeed",
+        }
+    }
 
-    // Initialize redundancies n_i
-    let n = (0..m).map(|_| rng.gen_range(1..=m as u32)).collect();
+    /// Number of components the structure needs, if it is fixed
+    pub fn components(self) -> Option<usize> {
+        match self {
+            SeriesSystem::Series => None,
+            SeriesSystem::SeriesParallel | SeriesSystem::Bridge => Some(5),
+            SeriesSystem::Overspeed => Some(4),
+        }
+    }
 
-    Series { r, n, m }
+    /// System reliability from the reliability of every component
+    pub fn reliability(self, r: &[f64]) -> f64 {
+        match self {
+            SeriesSystem::Series | SeriesSystem::Overspeed => r.iter().product(),
+            SeriesSystem::SeriesParallel => {
+                let parallel = 1.0 - (1.0 - r[2]) * (1.0 - r[3]);
+                1.0 - (1.0 - r[0] * r[1]) * (1.0 - parallel * r[4])
+            }
+            SeriesSystem::Bridge => {
+                let [r1, r2, r3, r4, r5] = [r[0], r[1], r[2], r[3], r[4]];
+                r1 * r2 + r3 * r4 + r1 * r4 * r5 + r2 * r3 * r5
+                    - r1 * r2 * r3 * r4
+                    - r1 * r2 * r3 * r5
+                    - r1 * r2 * r4 * r5
+                    - r1 * r3 * r4 * r5
+                    - r2 * r3 * r4 * r5
+                    + 2.0 * r1 * r2 * r3 * r4 * r5
+            }
+        }
+    }
 }
 
-impl Solution for Series {
-    type Data = (Vec<f64>, Vec<u32>);
+/// How the totals of a [`SeriesParams`] design are computed and which budgets bind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Formulation {
+    /// The RRAP test problems: volume `v[i] * n^2`, and volume, cost and
+    /// weight each within their budget
+    #[default]
+    Budgets,
+    /// Problem 3.1 as originally posed: volume `(w[i] * v[i] * 2^n)^2` and
+    /// weight within their budgets, and the cost budget subtracted from the
+    /// cost objective rather than bounding it
+    Baseline,
+}
+
+/// Component table of a reliability-redundancy allocation problem. The number
+/// of components is the length of the tables, which must all be equally long.
+///
+/// Component `i` with reliability `r` and `n` redundant units costs
+/// `alpha[i] * (-mission_time / ln r)^beta[i] * (n + e^(n / 4))`, takes a volume
+/// of `v[i] * n^2` and weighs `w[i] * n * e^(n / 4)`; [`Formulation::Baseline`]
+/// changes the volume and the role of the cost budget.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeriesParams {
+    pub system: SeriesSystem,
+    pub formulation: Formulation,
+    pub alpha: Vec<f64>,
+    pub beta: Vec<f64>,
+    pub v: Vec<f64>,
+    pub w: Vec<f64>,
+    pub volume_budget: f64,
+    pub cost_budget: f64,
+    pub weight_budget: f64,
+    /// Operating time the component reliabilities refer to, in hours
+    pub mission_time: f64,
+    /// Inclusive bounds of every component reliability
+    pub reliability_bounds: (f64, f64),
+    /// Inclusive bounds of the number of redundant units of every component
+    pub redundancy_bounds: (i64, i64),
+}
+
+impl Default for SeriesParams {
+    fn default() -> Self {
+        Self::preset(SeriesSystem::Series)
+    }
+}
+
+impl SeriesParams {
+    /// The component table the literature uses for `system`
+    pub fn preset(system: SeriesSystem) -> Self {
+        let (alpha, v, w, volume_budget, cost_budget, weight_budget, max_redundancy) = match system {
+            SeriesSystem::Series | SeriesSystem::Bridge => (
+                vec![2.33e-5, 1.45e-5, 0.541e-5, 8.05e-5, 1.95e-5],
+                vec![1.0, 2.0, 3.0, 4.0, 2.0],
+                vec![7.0, 8.0, 8.0, 6.0, 9.0],
+                110.0,
+                175.0,
+                200.0,
+                5,
+            ),
+            SeriesSystem::SeriesParallel => (
+                vec![2.5e-5, 1.45e-5, 0.541e-5, 0.541e-5, 2.1e-5],
+                vec![2.0, 4.0, 5.0, 8.0, 4.0],
+                vec![3.5, 4.0, 4.0, 3.5, 4.5],
+                180.0,
+                175.0,
+                100.0,
+                5,
+            ),
+            SeriesSystem::Overspeed => (
+                vec![1.0e-5, 2.3e-5, 0.3e-5, 2.3e-5],
+                vec![1.0, 2.0, 3.0, 2.0],
+                vec![6.0, 6.0, 8.0, 7.0],
+                250.0,
+                400.0,
+                500.0,
+                10,
+            ),
+        };
+
+        Self {
+            system,
+            formulation: Formulation::Budgets,
+            beta: vec![1.5; alpha.len()],
+            alpha,
+            v,
+            w,
+            volume_budget,
+            cost_budget,
+            weight_budget,
+            mission_time: 1000.0,
+            reliability_bounds: (0.5, 1.0 - 1e-6),
+            redundancy_bounds: (1, max_redundancy),
+        }
+    }
+
+    /// Problem 3.1 as the original program poses it: five components in series
+    /// with unscaled cost coefficients, the same weight coefficient for every
+    /// component and [`Formulation::Baseline`]
+    pub fn baseline() -> Self {
+        Self {
+            system: SeriesSystem::Series,
+            formulation: Formulation::Baseline,
+            alpha: vec![2.33, 1.45, 0.541, 8.05, 1.95],
+            beta: vec![1.5; 5],
+            v: vec![7.0, 8.0, 8.0, 6.0, 9.0],
+            w: vec![110.0; 5],
+            volume_budget: 175.0,
+            cost_budget: 200.0,
+            weight_budget: 200.0,
+            mission_time: 1000.0,
+            reliability_bounds: (0.5, 1.0 - 1e-6),
+            redundancy_bounds: (1, 10),
+        }
+    }
 
-    fn random() -> Self {
-        random(5)
+    /// Name of the catalog problem with this formulation and system
+    pub fn name(&self) -> &'static str {
+        match (self.formulation, self.system) {
+            (Formulation::Baseline, _) => "series",
+            (Formulation::Budgets, SeriesSystem::Series) => "series_hikita",
+            (Formulation::Budgets, system) => system.name(),
+        }
+    }
+
+    /// Read a component table from a `.toml` or `.json` file; fields left out
+    /// are taken from Hikita's series system.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ExperimentError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ExperimentError::Io(path.to_path_buf(), e))?;
+
+        let params: SeriesParams = FileFormat::of(path)?.parse(&text)?;
+
+        params.validate().map_err(ExperimentError::Problem)?;
+        Ok(params)
+    }
+
+    pub fn components(&self) -> usize {
+        self.alpha.len()
     }
 
-    fn mutate(&mut self) {
-        let mut rng = thread_rng();
-        let m = self.r.len();
+    /// Check that the table fits the system, every table covers all
+    /// components and the bounds and budgets make sense
+    pub fn validate(&self) -> Result<(), String> {
+        let m = self.components();
 
-        // Mutate reliabilities
-        for i in 0..m {
-            if rng.gen_bool(0.2) { // 20% chance for mutation of each reliability
-                let delta = rng.gen_range(-0.05..=0.05);
-                self.r[i] = (self.r[i] + delta).clamp(0.5, 1.0);
+        if m == 0 {
+            return Err("system needs at least one component".to_string());
+        }
+
+        if let Some(expected) = self.system.components() {
+            if m != expected {
+                return Err(format!("{} system has {} components, got {}", self.system.name(), expected, m));
+            }
+        }
+
+        for (name, table) in [("beta", &self.beta), ("v", &self.v), ("w", &self.w)] {
+            if table.len() != m {
+                return Err(format!("{} has {} entries, expected {}", name, table.len(), m));
             }
         }
 
-        // Mutate redundancies
-        for i in 0..m {
-            if rng.gen_bool(0.2) { // 20% chance for mutation of each redundancy
-                let delta = if rng.gen_bool(0.5) { 1 } else { -1 };
-                let new_value = (self.n[i] as i32 + delta).max(1);
-                self.n[i] = new_value as u32;
+        for (name, budget) in [
+            ("volume_budget", self.volume_budget),
+            ("cost_budget", self.cost_budget),
+            ("weight_budget", self.weight_budget),
+            ("mission_time", self.mission_time),
+        ] {
+            if !(budget.is_finite() && budget > 0.0) {
+                return Err(format!("{} must be positive, got {}", name, budget));
             }
         }
+
+        let (low, high) = self.reliability_bounds;
+        if !(0.0 < low && low <= high && high < 1.0) {
+            return Err(format!("reliability bounds must lie strictly between 0 and 1, got [{}, {}]", low, high));
+        }
+
+        let (low, high) = self.redundancy_bounds;
+        if !(1 <= low && low <= high) {
+            return Err(format!("redundancy bounds must be at least 1, got [{}, {}]", low, high));
+        }
+
+        Ok(())
+    }
+
+    /// A reliability and a redundancy within bounds per component
+    pub fn space(&self) -> MixedSpace {
+        let m = self.components();
+
+        MixedSpace {
+            reals: vec![self.reliability_bounds; m],
+            integers: vec![self.redundancy_bounds; m],
+            ..MixedSpace::default()
+        }
+    }
+
+    /// Cost of `n` redundant units of reliability `r` of component `i`
+    pub fn cost(&self, i: usize, r: f64, n: f64) -> f64 {
+        self.alpha[i] * (-self.mission_time / r.ln()).powf(self.beta[i]) * (n + (0.25 * n).exp())
+    }
+
+    /// Volume of `n` redundant units of component `i`
+    pub fn volume(&self, i: usize, n: f64) -> f64 {
+        match self.formulation {
+            Formulation::Budgets => self.v[i] * n * n,
+            Formulation::Baseline => (self.w[i] * self.v[i] * 2.0_f64.powf(n)).powi(2),
+        }
+    }
+
+    /// Weight of `n` redundant units of component `i`
+    pub fn weight(&self, i: usize, n: f64) -> f64 {
+        self.w[i] * n * (0.25 * n).exp()
     }
-    
-    fn crossover(&self, other: &Self) -> Self {
-        let mut rng = thread_rng();
 
-        let mut cross_r = Vec::with_capacity(self.r.len());
-        let mut cross_n = Vec::with_capacity(self.n.len());
+    /// Whether a design with these totals fits every budget
+    fn within_budgets(&self, volume: f64, cost: f64, weight: f64) -> bool {
+        let cost_bound = match self.formulation {
+            Formulation::Budgets => cost <= self.cost_budget,
+            Formulation::Baseline => true,
+        };
 
-        // Binary crossover (BLX-α) for reliabilities
-        let alpha = 0.5;
-        for i in 0..self.r.len() {
-            let c_min = self.r[i].min(other.r[i]);
-            let c_max = self.r[i].max(other.r[i]);
-            let range = c_max - c_min;
-            let lower = (c_min - alpha * range).clamp(0.5, 1.0);
-            let upper = (c_max + alpha * range).clamp(0.5, 1.0);
-            let value = rng.gen_range(lower..=upper);
-            cross_r.push(value);
+        volume <= self.volume_budget && cost_bound && weight <= self.weight_budget
+    }
+
+    /// The cost objective of a design costing `cost`
+    fn cost_objective(&self, cost: f64) -> f64 {
+        match self.formulation {
+            Formulation::Budgets => cost,
+            Formulation::Baseline => cost - self.cost_budget,
         }
-        
-        for i in 0..self.n.len() {
-            let value = if rng.gen_bool(0.5) { self.n[i] } else { other.n[i] };
-            cross_n.push(value);
+    }
+}
+
+/// A component table with its search space, built once and shared by its designs.
+///
+/// Serialized as its [`SeriesParams`] only and rebuilt when read back.
+#[derive(Clone, Debug)]
+pub struct SeriesInstance {
+    pub params: SeriesParams,
+    pub space: MixedSpace,
+}
+
+impl SeriesInstance {
+    pub fn new(params: SeriesParams) -> Self {
+        let space = params.space();
+        Self { params, space }
+    }
+}
+
+impl Problem for Arc<SeriesInstance> {
+    type Solution = Series;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Series {
+        Series {
+            genome: self.space.random(rng),
+            instance: Arc::clone(self),
         }
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.space.len())
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Series> {
+        Some(Series {
+            genome: self.space.genome_at(point),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<SeriesInstance> {
+    type Genome = Mixed;
+
+    type Params = SeriesParams;
+
+    fn name(&self) -> &str {
+        self.params.name()
+    }
 
-        Self { r: cross_r, n: cross_n, m: self.m }
+    fn params(&self) -> SeriesParams {
+        self.params.clone()
     }
-    
-    fn fitness(&self) -> Vec<f64> {
-        let alpha = [2.33, 1.45, 0.541, 8.05, 1.95];
-        let beta = [1.5; 5];
-        let C = 200.0;
 
-        let mut f1 = 1.0; // System reliability
-        let mut f2 = 0.0; // System cost
+    fn from_params(params: SeriesParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(SeriesInstance::new(params)))
+    }
 
-        // Calculate R_i(n_i) and cost for each component
-        for i in 0..self.m {
-            let r_i = self.r[i];
-            let n_i = self.n[i] as f64;
-            let alpha_i = alpha[i];
-            let beta_i = beta[i];
+    fn genome(&self, solution: &Series) -> Mixed {
+        solution.genome.clone()
+    }
 
-            // Calculate R_i(n_i)
-            let R_i = 1.0 - (1.0 - r_i).powf(n_i);
-            f1 *= R_i;
+    fn solution(&self, genome: Mixed) -> Result<Series, String> {
+        self.space.check(&genome)?;
+        Ok(Series { genome, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for SeriesInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
 
-            // Calculate cost
-            let cost = alpha_i * ((-1000.0 / r_i.ln()).powf(beta_i)) * (n_i + (0.25 * n_i).exp());
-            f2 += cost;
+impl<'de> Deserialize<'de> for SeriesInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SeriesParams::deserialize(deserializer).map(SeriesInstance::new)
+    }
+}
+
+/// Reliabilities of the components as the real variables of the genome, and
+/// their redundancies as the integer ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Series {
+    #[serde(flatten)]
+    pub genome: Mixed,
+    pub instance: Arc<SeriesInstance>,
+}
+
+impl Series {
+    /// Reliability of every component
+    pub fn r(&self) -> &[f64] {
+        &self.genome.reals
+    }
+
+    /// Number of redundant units of every component
+    pub fn n(&self) -> &[i64] {
+        &self.genome.integers
+    }
+
+    /// Cost, volume and weight of the whole system
+    fn totals(&self) -> (f64, f64, f64) {
+        let params = &self.instance.params;
+        let mut totals = (0.0, 0.0, 0.0);
+
+        for (i, (&r, &n)) in self.r().iter().zip(self.n()).enumerate() {
+            let n = n as f64;
+            totals.0 += params.cost(i, r, n);
+            totals.1 += params.volume(i, n);
+            totals.2 += params.weight(i, n);
         }
-        f2 -= C;
 
-        // We want to maximize system reliability, so invert f1 and turn it into a minimization problem
-        let f1_min = -f1;
+        totals
+    }
+}
+
+impl Solution for Series {
+    type Data = Mixed;
 
-        vec![f1_min, f2]
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.instance.space.mutate(&mut self.genome, rng);
     }
 
-    fn dominates(&self, other: &Self) -> bool {
-        let self_fitness = self.fitness();
-        let other_fitness = other.fitness();
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            genome: self.instance.space.crossover(&self.genome, &other.genome, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
 
-        let mut better_in_any = false;
+    /// Negated system reliability, to maximize it, and system cost
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        // Reliability of every component with its redundant units in parallel
+        let stages = self
+            .r()
+            .iter()
+            .zip(self.n())
+            .map(|(&r, &n)| 1.0 - (1.0 - r).powf(n as f64))
+            .collect::<Vec<_>>();
 
-        for (self_value, other_value) in self_fitness.iter().zip(other_fitness.iter()) {
-            // If self is worse in any objective, it does not dominate
-            if self_value > other_value {
-                return false;
-            }
+        let (cost, _, _) = self.totals();
 
-            // All objectives should be minimized
-            if self_value < other_value {
-                better_in_any = true;
+        let params = &self.instance.params;
+        Ok(vec![-params.system.reliability(&stages), params.cost_objective(cost)])
+    }
+
+    fn feasible(&self) -> bool {
+        let (cost, volume, weight) = self.totals();
+
+        self.instance.space.contains(&self.genome) && self.instance.params.within_budgets(volume, cost, weight)
+    }
+}
+
+impl Standalone for Series {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<SeriesInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(SeriesInstance::new(SeriesParams::default())))
+            .random(rng)
+    }
+}
+
+/// A system whose components, or stages, may each mix several subsystems in
+/// parallel (Coit and Smith, 1996), so that the number of subsystems is a
+/// decision. Every subsystem counts against the budgets on its own.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VariableSeriesParams {
+    /// One stage per component of the table, connected as its system says
+    pub components: SeriesParams,
+    /// Most subsystems in the whole system
+    pub max_subsystems: usize,
+    pub crossover: VariableCrossover,
+}
+
+impl Default for VariableSeriesParams {
+    fn default() -> Self {
+        Self {
+            components: SeriesParams::default(),
+            max_subsystems: 15,
+            crossover: VariableCrossover::default(),
+        }
+    }
+}
+
+impl VariableSeriesParams {
+    pub fn validate(&self) -> Result<(), String> {
+        self.components.validate()?;
+
+        if self.max_subsystems < self.components.components() {
+            return Err(format!(
+                "max_subsystems is {}, but every one of the {} stages needs a subsystem",
+                self.max_subsystems,
+                self.components.components()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Every subsystem a gene holding its stage as a category, its reliability
+    /// as a real and its redundancy as an integer
+    pub fn space(&self) -> VariableSpace {
+        VariableSpace {
+            gene: MixedSpace {
+                reals: vec![self.components.reliability_bounds],
+                integers: vec![self.components.redundancy_bounds],
+                categories: vec![self.components.components()],
+                booleans: 0,
+            },
+            min_len: 1,
+            max_len: self.max_subsystems,
+            crossover: self.crossover,
+        }
+    }
+}
+
+/// A [`VariableSeriesParams`] system with its search space, built once and
+/// shared by its designs.
+///
+/// Serialized as its [`VariableSeriesParams`] only and rebuilt when read back.
+#[derive(Clone, Debug)]
+pub struct VariableSeriesInstance {
+    pub params: VariableSeriesParams,
+    pub space: VariableSpace,
+}
+
+impl VariableSeriesInstance {
+    pub fn new(params: VariableSeriesParams) -> Self {
+        let space = params.space();
+        Self { params, space }
+    }
+}
+
+impl Problem for Arc<VariableSeriesInstance> {
+    type Solution = VariableSeries;
+
+    /// One subsystem for every stage, then a random number of extra ones
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> VariableSeries {
+        let stages = self.params.components.components();
+        let max_subsystems = self.params.max_subsystems;
+
+        let mut genome = Variable(Vec::with_capacity(max_subsystems));
+        for stage in 0..stages {
+            let mut gene = self.space.gene.random(rng);
+            gene.categories[0] = stage;
+            genome.0.push(gene);
+        }
+        for _ in 0..rng.gen_range(0..=max_subsystems - stages) {
+            genome.0.push(self.space.gene.random(rng));
+        }
+        genome.0.shuffle(rng);
+
+        VariableSeries { genome, instance: Arc::clone(self) }
+    }
+
+    /// One coordinate for the number of subsystems, then one per variable of
+    /// every subsystem there could be
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(1 + self.params.max_subsystems * self.space.gene.len())
+    }
+
+    /// The first coordinate picks how many subsystems beyond one per stage
+    /// the design has, and the following ones the subsystems in turn, the
+    /// first of which cover the stages in order whatever their category
+    /// coordinate says.
+    fn solution_at(&self, point: &[f64]) -> Option<VariableSeries> {
+        let stages = self.params.components.components();
+        let extra = self.params.max_subsystems - stages;
+        let count = stages + ((point[0] * (extra + 1) as f64) as usize).min(extra);
+
+        let genes = point[1..].chunks(self.space.gene.len()).take(count).enumerate().map(|(i, coordinates)| {
+            let mut gene = self.space.gene.genome_at(coordinates);
+            if i < stages {
+                gene.categories[0] = i;
             }
+            gene
+        });
+
+        Some(VariableSeries {
+            genome: Variable(genes.collect()),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<VariableSeriesInstance> {
+    type Genome = VariableSeriesGenome;
+
+    type Params = VariableSeriesParams;
+
+    fn name(&self) -> &str {
+        "variable_series"
+    }
+
+    fn params(&self) -> VariableSeriesParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: VariableSeriesParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(VariableSeriesInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &VariableSeries) -> VariableSeriesGenome {
+        VariableSeriesGenome { genome: solution.genome.clone() }
+    }
+
+    fn solution(&self, genome: VariableSeriesGenome) -> Result<VariableSeries, String> {
+        self.space.check(&genome.genome)?;
+        Ok(VariableSeries { genome: genome.genome, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for VariableSeriesInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VariableSeriesInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VariableSeriesParams::deserialize(deserializer).map(VariableSeriesInstance::new)
+    }
+}
+
+/// The subsystems of a [`VariableSeries`] design without its instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VariableSeriesGenome {
+    pub genome: Variable,
+}
+
+/// The subsystems of a [`VariableSeriesParams`] system, in no particular order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VariableSeries {
+    pub genome: Variable,
+    pub instance: Arc<VariableSeriesInstance>,
+}
+
+impl VariableSeries {
+    /// Stage, reliability and redundancy of every subsystem
+    pub fn subsystems(&self) -> impl Iterator<Item = (usize, f64, f64)> + '_ {
+        self.genome.0.iter().map(|gene| (gene.categories[0], gene.reals[0], gene.integers[0] as f64))
+    }
+
+    /// Cost, volume and weight of the whole system
+    fn totals(&self) -> (f64, f64, f64) {
+        let components = &self.instance.params.components;
+        let mut totals = (0.0, 0.0, 0.0);
+
+        for (stage, r, n) in self.subsystems() {
+            totals.0 += components.cost(stage, r, n);
+            totals.1 += components.volume(stage, n);
+            totals.2 += components.weight(stage, n);
         }
 
-        better_in_any
+        totals
+    }
+}
+
+impl Solution for VariableSeries {
+    type Data = Variable;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.instance.space.mutate(&mut self.genome, rng);
     }
-    
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            genome: self.instance.space.crossover(&self.genome, &other.genome, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
+
+    /// Negated system reliability and cost; a stage without subsystems never works
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        let components = &self.instance.params.components;
+
+        // Probability that every subsystem of a stage fails
+        let mut failure = vec![1.0; components.components()];
+        for (stage, r, n) in self.subsystems() {
+            failure[stage] *= (1.0 - r).powf(n);
+        }
+
+        let stages = failure.iter().map(|q| 1.0 - q).collect::<Vec<_>>();
+        let (cost, _, _) = self.totals();
+
+        Ok(vec![-components.system.reliability(&stages), components.cost_objective(cost)])
+    }
+
     fn feasible(&self) -> bool {
-        let v = [7.0, 8.0, 8.0, 6.0, 9.0];
-        let w_i = [110.0; 5];
-        let V = 175.0;
-        let W = 200.0;
-        
-        let mut g1 = 0.0;
-        let mut g2 = 0.0;
-
-        for i in 0..self.m {
-            let n_i = self.n[i] as f64;
-            let w_i_i = w_i[i];
-            let v_i = v[i];
-
-            let term1 = w_i_i * v_i * 2.0_f64.powf(n_i);
-            g1 += term1 * term1;
-
-            let term2 = w_i_i * n_i * (0.25 * n_i).exp();
-            g2 += term2;
-        }
-
-        g1 -= V;
-        g2 -= W;
-
-        // Decision variable constraints
-        let r_bounds = self.r.iter().all(|&r_i| (0.5..=1.0).contains(&r_i));
-        let n_bounds = self.n.iter().all(|&n_i| n_i >= 1);
-        
-        g1 <= 0.0 && g2 <= 0.0 && r_bounds && n_bounds
+        let components = &self.instance.params.components;
+
+        let mut covered = vec![false; components.components()];
+        for (stage, _, _) in self.subsystems() {
+            covered[stage] = true;
+        }
+
+        let (cost, volume, weight) = self.totals();
+
+        self.instance.space.contains(&self.genome)
+            && covered.iter().all(|&c| c)
+            && components.within_budgets(volume, cost, weight)
+    }
+}
+
+impl Standalone for VariableSeries {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<VariableSeriesInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(VariableSeriesInstance::new(VariableSeriesParams::default())))
+            .random(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_points_cover_every_stage_within_the_length_bounds() {
+        let instance = Arc::new(VariableSeriesInstance::new(VariableSeriesParams::default()));
+        let dimension = instance.unit_dimension().unwrap();
+        let stages = instance.params.components.components();
+
+        for (u, len) in [(0.0, stages), (0.5, 10), (1.0, instance.params.max_subsystems)] {
+            let design = instance.solution_at(&vec![u; dimension]).unwrap();
+
+            assert_eq!(design.genome.len(), len);
+            assert!(instance.space.contains(&design.genome));
+            for stage in 0..stages {
+                assert!(design.subsystems().any(|(s, _, _)| s == stage));
+            }
+        }
     }
 }
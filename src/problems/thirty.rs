@@ -1,31 +1,117 @@
-use crate::solution::Solution;
+use crate::evaluation::EvaluationError;
+use crate::metrics::nondominated;
+use crate::genome::check_reals;
+use crate::solution::{Portable, Problem, Solution, Standalone};
 
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
-#[derive(Clone, Debug)]
-pub(crate) struct Thirty {
-    pub(crate) data: Vec<f64>, // Decision variables x_i, where i = 1..30
+/// Size of a ZDT3 problem.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Zdt3Params {
+    /// Number of decision variables, the first one spanning the front
+    pub variables: usize,
 }
 
-impl Solution for Thirty {
-    type Data = Vec<f64>;
-    
-    fn random() -> Self {
-        let mut rng = thread_rng();
-        let n = 30;
+impl Default for Zdt3Params {
+    fn default() -> Self {
+        Self { variables: 30 }
+    }
+}
+
+impl Zdt3Params {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.variables < 2 {
+            return Err(format!("zdt3 needs at least 2 variables, got {}", self.variables));
+        }
+
+        Ok(())
+    }
+}
+
+impl Problem for Zdt3Params {
+    type Solution = Thirty;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Thirty {
+        Thirty::random_n(self.variables, rng)
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.variables)
+    }
+
+    fn solution_at(&self, point: &[f64]) -> Option<Thirty> {
+        Some(Thirty { data: point.to_vec() })
+    }
+}
+
+impl Portable for Zdt3Params {
+    type Genome = Thirty;
+
+    type Params = Zdt3Params;
+
+    fn name(&self) -> &str {
+        "zdt3"
+    }
+
+    fn params(&self) -> Zdt3Params {
+        self.clone()
+    }
+
+    fn from_params(params: Zdt3Params) -> Result<Self, String> {
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn genome(&self, solution: &Thirty) -> Thirty {
+        solution.clone()
+    }
+
+    fn solution(&self, genome: Thirty) -> Result<Thirty, String> {
+        check_reals(&genome.data, &vec![(0.0, 1.0); self.variables])?;
+        Ok(genome)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Thirty {
+    pub data: Vec<f64>, // Decision variables x_i, where i = 1..n, 30 unless set by Zdt3Params
+}
+
+impl Thirty {
+    pub fn random_n<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
         let data = (0..n).map(|_| rng.gen_range(0.0..=1.0)).collect();
         Self { data }
     }
+}
 
+/// Points on the true Pareto front of ZDT3, from `samples` evenly spaced values
+/// of x_1 with all other variables at zero. Dominated points between the
+/// disconnected pieces are left out.
+pub fn pareto_front(samples: usize) -> Vec<Vec<f64>> {
+    let points = (0..samples)
+        .map(|i| {
+            let f1 = i as f64 / (samples - 1).max(1) as f64;
+            let f2 = 1.0 - f1.sqrt() - f1 * (10.0 * PI * f1).sin();
+            vec![f1, f2]
+        })
+        .collect::<Vec<_>>();
+
+    nondominated(&points)
+}
+
+impl Solution for Thirty {
+    type Data = Vec<f64>;
+    
     /// Add/subtract small random values to/from a random selection of variables
-    fn mutate(&mut self) {
-        let mut rng = thread_rng();
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         let mutation_strength = 0.1;
         let n = self.data.len();
 
         // Decide how many variables should be mutated, up to a maximum of 25%
-        let num_mutations = rng.gen_range(1..=n / 4);
+        let num_mutations = rng.gen_range(1..=(n / 4).max(1));
 
         for _ in 0..num_mutations {
             let idx = rng.gen_range(0..n);
@@ -35,13 +121,12 @@ impl Solution for Thirty {
     }
 
     /// Try simulated binary crossover (SBX)
-    fn crossover(&self, other: &Self) -> Self {
-        let mut rng = thread_rng();
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
         let n = self.data.len();
         let mut child_data = vec![0.0; n];
         let eta = 2.0; // Crossover distribution index
 
-        for i in 0..n {
+        for (i, child_value) in child_data.iter_mut().enumerate() {
             let u = rng.gen::<f64>();
             let beta = if u <= 0.5 {
                 (2.0 * u).powf(1.0 / (eta + 1.0))
@@ -49,17 +134,17 @@ impl Solution for Thirty {
                 (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (eta + 1.0))
             };
 
-            child_data[i] = 0.5 * ((1.0 + beta) * self.data[i] + (1.0 - beta) * other.data[i]);
+            *child_value = 0.5 * ((1.0 + beta) * self.data[i] + (1.0 - beta) * other.data[i]);
 
             // Ensure child variable is within bounds
-            child_data[i] = child_data[i].clamp(0.0, 1.0);
+            *child_value = child_value.clamp(0.0, 1.0);
         }
 
         Self { data: child_data }
     }
 
     /// Return the value of all (?) objective functions
-    fn fitness(&self) -> Vec<f64> {
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
         let x1 = self.data[0];
 
         let g: f64 = 1.0 + self.data[1..].iter().sum::<f64>();
@@ -69,32 +154,17 @@ impl Solution for Thirty {
         let f1 = x1;
         let f2 = g * h;
 
-        vec![f1, f2]
+        Ok(vec![f1, f2])
     }
     
-    fn dominates(&self, other: &Self) -> bool {
-        let self_fitness = self.fitness();
-        let other_fitness = other.fitness();
-
-        let mut better_in_any = false;
-
-        for (self_value, other_value) in self_fitness.iter().zip(other_fitness.iter()) {
-            // If self is worse in any objective, it does not dominate
-            if self_value > other_value {
-                return false;
-            }
-
-            // All objectives should be minimized
-            if self_value < other_value {
-                better_in_any = true;
-            }
-        }
-
-        better_in_any
-    }
-
     /// Check if all variables are within [0, 1]
     fn feasible(&self) -> bool {
         self.data.iter().all(|&x| (0.0..=1.0).contains(&x))
     }
+}
+
+impl Standalone for Thirty {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::random_n(30, rng)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+//! Bi-objective travelling salesman: one tour through all cities, short under
+//! two unrelated distance measures at once. Instances are generated like the
+//! classic kroAB ones, the two distances being Euclidean between two
+//! independent random placements of the cities.
+
+use std::sync::{Arc, OnceLock};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::evaluation::EvaluationError;
+use crate::genome::permutation::{Permutation, PermutationCrossover, PermutationMutation};
+use crate::solution::{Portable, Problem, Solution, Standalone};
+
+/// Side of the square the cities are placed in
+const SIDE: f64 = 1000.0;
+
+/// Size of a generated instance and the seed it is generated from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TspParams {
+    pub cities: usize,
+    /// Seed for the city placements; the same seed always gives the same instance
+    pub instance_seed: u64,
+    pub crossover: PermutationCrossover,
+    /// Inversion is the 2-opt move, which suits tours best
+    pub mutation: PermutationMutation,
+}
+
+impl Default for TspParams {
+    fn default() -> Self {
+        Self {
+            cities: 50,
+            instance_seed: 1,
+            crossover: PermutationCrossover::default(),
+            mutation: PermutationMutation::Inversion,
+        }
+    }
+}
+
+impl TspParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cities < 3 {
+            return Err(format!("travelling salesman needs at least 3 cities, got {}", self.cities));
+        }
+
+        Ok(())
+    }
+}
+
+/// Two distance matrices between the cities.
+///
+/// Serialized as its [`TspParams`] only and regenerated when read back.
+#[derive(Clone, Debug)]
+pub struct TspInstance {
+    pub params: TspParams,
+    /// `distances[k][a][b]` is the distance from city `a` to city `b` under objective `k`
+    pub distances: [Vec<Vec<f64>>; 2],
+}
+
+impl TspInstance {
+    pub fn new(params: TspParams) -> Self {
+        let mut rng = StdRng::seed_from_u64(params.instance_seed);
+        let mut matrix = || {
+            let cities = (0..params.cities)
+                .map(|_| (rng.gen_range(0.0..SIDE), rng.gen_range(0.0..SIDE)))
+                .collect::<Vec<_>>();
+
+            cities
+                .iter()
+                .map(|&(xa, ya)| cities.iter().map(|&(xb, yb)| (xa - xb).hypot(ya - yb).round()).collect())
+                .collect()
+        };
+
+        let distances = [matrix(), matrix()];
+        Self { params, distances }
+    }
+}
+
+impl Problem for Arc<TspInstance> {
+    type Solution = Tsp;
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Tsp {
+        Tsp {
+            tour: Permutation::random(self.params.cities, rng),
+            instance: Arc::clone(self),
+        }
+    }
+
+    fn unit_dimension(&self) -> Option<usize> {
+        Some(self.params.cities)
+    }
+
+    /// The tour visiting the cities in increasing order of their coordinates
+    fn solution_at(&self, point: &[f64]) -> Option<Tsp> {
+        Some(Tsp {
+            tour: Permutation::from_keys(point),
+            instance: Arc::clone(self),
+        })
+    }
+}
+
+impl Portable for Arc<TspInstance> {
+    type Genome = TspGenome;
+
+    type Params = TspParams;
+
+    fn name(&self) -> &str {
+        "tsp"
+    }
+
+    fn params(&self) -> TspParams {
+        self.params.clone()
+    }
+
+    fn from_params(params: TspParams) -> Result<Self, String> {
+        params.validate()?;
+        Ok(Arc::new(TspInstance::new(params)))
+    }
+
+    fn genome(&self, solution: &Tsp) -> TspGenome {
+        TspGenome { tour: solution.tour.clone() }
+    }
+
+    fn solution(&self, genome: TspGenome) -> Result<Tsp, String> {
+        genome.tour.check(self.params.cities)?;
+        Ok(Tsp { tour: genome.tour, instance: Arc::clone(self) })
+    }
+}
+
+impl Serialize for TspInstance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.params.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TspInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TspParams::deserialize(deserializer).map(TspInstance::new)
+    }
+}
+
+/// The variables of a [`Tsp`] without its instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TspGenome {
+    pub tour: Permutation,
+}
+
+/// A closed tour, visiting the cities in order and returning to the first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Tsp {
+    pub tour: Permutation,
+    pub instance: Arc<TspInstance>,
+}
+
+impl Solution for Tsp {
+    type Data = Permutation;
+
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.tour.mutate(self.instance.params.mutation, rng);
+    }
+
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        Self {
+            tour: self.tour.crossover(&other.tour, self.instance.params.crossover, rng),
+            instance: Arc::clone(&self.instance),
+        }
+    }
+
+    /// Length of the tour under each distance
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError> {
+        let tour = &self.tour.0;
+        let legs = tour.iter().zip(tour.iter().cycle().skip(1));
+
+        Ok(self
+            .instance
+            .distances
+            .iter()
+            .map(|distance| legs.clone().map(|(&a, &b)| distance[a][b]).sum())
+            .collect())
+    }
+
+    fn feasible(&self) -> bool {
+        self.tour.len() == self.instance.params.cities && self.tour.is_valid()
+    }
+}
+
+impl Standalone for Tsp {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        static DEFAULT: OnceLock<Arc<TspInstance>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(TspInstance::new(TspParams::default())))
+            .random(rng)
+    }
+}
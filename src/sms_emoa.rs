@@ -0,0 +1,281 @@
+//! SMS-EMOA (Beume, Naujoks and Emmerich, 2007): a steady-state algorithm
+//! that adds one child at a time and drops the member of the worst front
+//! contributing the least hypervolume.
+
+use rand::prelude::*;
+
+use crate::algorithm::{Algorithm, WithEngine};
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::engine::{Engine, RESAMPLE_LIMIT_FACTOR};
+use crate::evaluation::{RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::Individual;
+use crate::metrics::{bounds, hypervolume_contributions, normalize, shared_contribution};
+use crate::nsga2::non_dominated_sort;
+use crate::solution::Solution;
+
+/// Reference point for hypervolume contributions, in objectives normalized to
+/// the worst front, so that its extreme members contribute too.
+const REFERENCE: f64 = 1.1;
+
+pub struct SmsEmoa<S: Solution> {
+    engine: Engine<S>,
+    population: Vec<Individual<S>>,
+    /// Contributions of the worst front left by the last reduction, with three
+    /// or more objectives where computing them afresh is expensive
+    contributions: Option<Contributions>,
+}
+
+/// Hypervolume contributions of the members of a front, normalized to its
+/// `ideal` and `nadir` points.
+struct Contributions {
+    ideal: Vec<f64>,
+    nadir: Vec<f64>,
+    /// Population indices of the members, in the order of `values`
+    members: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl Contributions {
+    /// Contributions of `front` if it is the cached front plus `child` under
+    /// the same normalization, lowering the others by what they share with it
+    fn extended(&self, front: &[usize], child: usize, points: &[Vec<f64>], reference: &[f64]) -> Option<Vec<f64>> {
+        let child_position = front.iter().position(|&i| i == child)?;
+
+        if front.len() != self.members.len() + 1 {
+            return None;
+        }
+
+        front
+            .iter()
+            .enumerate()
+            .map(|(position, &index)| {
+                if position == child_position {
+                    let others = without(points, &[position]);
+                    return Some(shared_contribution(&points[position], &points[position], &others, reference));
+                }
+
+                let cached = self.members.iter().position(|&member| member == index)?;
+                let others = without(points, &[position, child_position]);
+                let shared = shared_contribution(&points[position], &points[child_position], &others, reference);
+                Some(self.values[cached] - shared)
+            })
+            .collect()
+    }
+}
+
+/// `points` except those at `skipped` positions
+fn without(points: &[Vec<f64>], skipped: &[usize]) -> Vec<Vec<f64>> {
+    points
+        .iter()
+        .enumerate()
+        .filter(|(position, _)| !skipped.contains(position))
+        .map(|(_, point)| point.clone())
+        .collect()
+}
+
+impl<S: Solution> SmsEmoa<S> {
+    /// Remove one member: an infeasible one from the worst front holding any
+    /// while there are some, otherwise the smallest hypervolume contributor of
+    /// the worst front.
+    fn reduce(&mut self) {
+        let fronts = non_dominated_sort(&self.population);
+
+        let infeasible = fronts.iter().rev().find_map(|front| {
+            let members = front.iter().copied().filter(|&i| !self.population[i].feasible).collect::<Vec<_>>();
+            members.choose(&mut self.engine.rng).copied()
+        });
+
+        let worst = match (infeasible, fronts.last()) {
+            (None, Some(front)) if front.len() > 1 => self.least_contributor(front),
+            (infeasible, front) => {
+                let Some(worst) = infeasible.or(front.map(|front| front[0])) else {
+                    return;
+                };
+
+                // Removing anyone but a cached member, such as a dominated child, keeps the cache
+                if self.contributions.as_ref().is_some_and(|cached| cached.members.contains(&worst)) {
+                    self.contributions = None;
+                }
+                worst
+            }
+        };
+
+        self.population.swap_remove(worst);
+    }
+
+    /// The member of `front` contributing the least hypervolume. With three or
+    /// more objectives the contributions of the remaining members are kept, so
+    /// that the next reduction only has to account for its child when the
+    /// front and its bounds have not changed otherwise.
+    fn least_contributor(&mut self, front: &[usize]) -> usize {
+        let points = front.iter().map(|&i| self.population[i].objectives.clone()).collect::<Vec<_>>();
+        let (ideal, nadir) = bounds(&points).unwrap_or_default();
+        let normalized = normalize(&points, &ideal, &nadir);
+        let reference = vec![REFERENCE; ideal.len()];
+        let child = self.population.len() - 1;
+
+        let cached = self
+            .contributions
+            .take()
+            .filter(|cached| cached.ideal == ideal && cached.nadir == nadir)
+            .and_then(|cached| cached.extended(front, child, &normalized, &reference));
+        let values = cached.unwrap_or_else(|| hypervolume_contributions(&normalized, &reference));
+
+        let least = (0..front.len())
+            .min_by(|&a, &b| values[a].total_cmp(&values[b]))
+            .unwrap_or(0);
+        let worst = front[least];
+
+        if ideal.len() > 2 {
+            // The others gain what they shared with the removed member, and the
+            // last member of the population moves into its place
+            let (members, values) = (0..front.len())
+                .filter(|&position| position != least)
+                .map(|position| {
+                    let others = without(&normalized, &[position, least]);
+                    let shared = shared_contribution(&normalized[position], &normalized[least], &others, &reference);
+                    let member = if front[position] == child { worst } else { front[position] };
+                    (member, values[position] + shared)
+                })
+                .unzip();
+
+            self.contributions = Some(Contributions { ideal, nadir, members, values });
+        }
+
+        worst
+    }
+}
+
+impl<S: Solution> WithEngine<S> for SmsEmoa<S> {
+    fn from_engine(engine: Engine<S>) -> Self {
+        Self {
+            population: Vec::with_capacity(engine.config.population_size() + 1),
+            engine,
+            contributions: None,
+        }
+    }
+
+    fn engine_mut(&mut self) -> &mut Engine<S> {
+        &mut self.engine
+    }
+}
+
+impl<S: Solution> Algorithm<S> for SmsEmoa<S> {
+    fn initialize(&mut self) -> Result<(), RunError> {
+        self.engine.start();
+        self.population = self.engine.initial_population(self.engine.config.population_size())?;
+        self.contributions = None;
+
+        let fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &fronts);
+        Ok(())
+    }
+
+    /// Take a single-child step for every two members of the population, so
+    /// that a generation costs as many evaluations as one of NSGA-II. Children
+    /// are evaluated one at a time, so batch evaluators gain nothing, and
+    /// running out of evaluations or time may end a generation early.
+    fn step(&mut self) -> Result<(), RunError> {
+        let population_size = self.engine.config.population_size();
+        let mut resampled = 0;
+
+        for _ in 0..population_size.div_ceil(2) {
+            if self.engine.exhausted() {
+                break;
+            }
+
+            // Parents are drawn uniformly; selection pressure comes from the reduction alone
+            let mut parents = (0..self.population.len()).choose_multiple(&mut self.engine.rng, 2).into_iter();
+            let first = parents.next().unwrap_or(0);
+            let second = parents.next().unwrap_or(first);
+
+            let child = self
+                .engine
+                .breed(&self.population[first].solution, &self.population[second].solution);
+
+            match self.engine.evaluate(vec![child])?.pop() {
+                Some(child) => {
+                    self.population.push(child);
+                    self.reduce();
+                }
+                None => {
+                    resampled += 1;
+
+                    if resampled > RESAMPLE_LIMIT_FACTOR * population_size {
+                        return Err(RunError::ResampleLimit(resampled));
+                    }
+                }
+            }
+        }
+
+        self.engine.generation += 1;
+        let fronts = non_dominated_sort(&self.population);
+        self.engine.record(&self.population, &fronts);
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.engine.finished()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.engine.add_observer(observer);
+    }
+
+    fn population(&self) -> &[Individual<S>] {
+        &self.population
+    }
+
+    fn archive(&self) -> Option<&Archive<S>> {
+        self.engine.archive.as_ref()
+    }
+
+    fn best(&self) -> Option<&Individual<S>> {
+        self.engine.best.as_ref()
+    }
+
+    fn config(&self) -> &Config {
+        &self.engine.config
+    }
+
+    fn statistics(&self) -> &RunStatistics {
+        &self.engine.statistics
+    }
+
+    fn history(&self) -> &RunHistory {
+        &self.engine.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_contributions_match_computing_them_afresh() {
+        let reference = [REFERENCE; 3];
+        let points = [[0.0, 0.2, 1.0], [0.5, 0.5, 0.3], [1.0, 0.0, 0.6], [0.3, 1.0, 0.0], [0.4, 0.4, 0.5]]
+            .map(|point| point.to_vec())
+            .to_vec();
+
+        // The child is the last member of the population, here at index 4
+        let front = [2, 0, 4, 3, 1];
+        let ordered = front.iter().map(|&i| points[i].clone()).collect::<Vec<_>>();
+        let cached = Contributions {
+            ideal: Vec::new(),
+            nadir: Vec::new(),
+            members: vec![0, 1, 2, 3],
+            values: hypervolume_contributions(&points[..4], &reference),
+        };
+
+        let extended = cached.extended(&front, 4, &ordered, &reference).unwrap();
+        let fresh = hypervolume_contributions(&ordered, &reference);
+
+        for (a, b) in extended.iter().zip(&fresh) {
+            assert!((a - b).abs() < 1e-12, "{:?} != {:?}", extended, fresh);
+        }
+        assert!(cached.extended(&front[..4], 4, &ordered[..4], &reference).is_none());
+    }
+}
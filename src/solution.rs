@@ -1,15 +1,87 @@
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::evaluation::EvaluationError;
+
 pub trait Solution: Clone {
     type Data;
 
-    fn random() -> Self;
+    fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R);
 
-    fn mutate(&mut self);
+    fn crossover<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self;
 
-    fn crossover(&self, other: &Self) -> Self;
+    /// Objective values to be minimized, or why they could not be computed
+    fn fitness(&self) -> Result<Vec<f64>, EvaluationError>;
 
-    fn fitness(&self) -> Vec<f64>;
+    fn feasible(&self) -> bool;
+}
 
-    fn dominates(&self, other: &Self) -> bool;
+/// A solution type that is a problem on its own, drawing random solutions
+/// without an instance, from defaults of its own if it has parameters.
+///
+/// Types whose solutions only make sense for an instance given at runtime,
+/// like [`External`](crate::problems::external::External), leave this out and
+/// are drawn from their [`Problem`].
+pub trait Standalone: Solution {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
 
-    fn feasible(&self) -> bool;
+/// A problem instance as a value, carrying the parameters chosen at runtime,
+/// such as the number of variables, bounds or data tables.
+///
+/// Built-in problems whose solutions share the instance for their own
+/// evaluation and variation implement it on an `Arc` of the instance; the
+/// others, whose solutions need nothing from it, on their parameters.
+pub trait Problem {
+    type Solution: Solution;
+
+    /// A random solution of this instance
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Solution;
+
+    /// Number of coordinates of the points [`Problem::solution_at`] takes, or
+    /// `None` if solutions cannot be built from points of the unit hypercube
+    fn unit_dimension(&self) -> Option<usize> {
+        None
+    }
+
+    /// The solution at `point`, whose coordinates within `[0, 1]` are scaled to
+    /// the bounds of the variables, for the [`Initialization`] strategies
+    /// other than random.
+    ///
+    /// [`Initialization`]: crate::initialization::Initialization
+    fn solution_at(&self, _point: &[f64]) -> Option<Self::Solution> {
+        None
+    }
+}
+
+/// A [`Problem`] whose solutions can be taken apart into their variables and
+/// put back together for this instance, so that solutions written by another
+/// run, perhaps of another instance, are checked before they are evaluated.
+///
+/// The instance itself travels as its parameters, which is how a
+/// [`Master`](crate::distributed::Master) hands it to its workers once and
+/// then sends them only genomes.
+pub trait Portable: Problem {
+    /// The variables of a solution, serialized like the solution without its instance
+    type Genome: Serialize + DeserializeOwned;
+
+    /// Everything needed to rebuild the instance elsewhere
+    type Params: Serialize + DeserializeOwned;
+
+    /// Name of the problem in the [`CATALOG`](crate::problems::CATALOG)
+    fn name(&self) -> &str;
+
+    fn params(&self) -> Self::Params;
+
+    /// The instance described by `params`, or why they do not describe a valid one
+    fn from_params(params: Self::Params) -> Result<Self, String>
+    where
+        Self: Sized;
+
+    fn genome(&self, solution: &Self::Solution) -> Self::Genome;
+
+    /// The solution of this instance with `genome`, or why `genome` does not
+    /// fit it, such as a wrong number of variables or one out of bounds
+    fn solution(&self, genome: Self::Genome) -> Result<Self::Solution, String>;
 }
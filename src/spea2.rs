@@ -0,0 +1,271 @@
+//! SPEA2 (Zitzler, Laumanns and Thiele, 2001): an archive of the best
+//! individuals found so far, ranked by strength fitness and thinned by
+//! nearest-neighbour truncation.
+
+use std::cmp::Ordering;
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::{Algorithm, WithEngine};
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::engine::Engine;
+use crate::evaluation::{RunError, RunStatistics};
+use crate::history::{Observer, RunHistory};
+use crate::individual::Individual;
+use crate::metrics::euclidean;
+use crate::nsga2::non_dominated_sort;
+use crate::solution::Solution;
+
+/// Settings specific to [`Spea2`]; everything else comes from [`Config`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Spea2Params {
+    /// Number of individuals kept in the archive; the population size when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_size: Option<usize>,
+}
+
+impl Spea2Params {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.archive_size == Some(0) {
+            return Err("archive_size must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Spea2<S: Solution> {
+    engine: Engine<S>,
+    params: Spea2Params,
+    /// Offspring of the last generation, before they compete for the archive
+    offspring: Vec<Individual<S>>,
+    archive: Vec<Individual<S>>,
+    /// Fitness of every archive member, indexed like `archive`; lower is better
+    fitness: Vec<f64>,
+}
+
+impl<S: Solution> Spea2<S> {
+    /// Use `params` instead of the default [`Spea2Params`].
+    pub fn with_params(mut self, params: Spea2Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn params(&self) -> &Spea2Params {
+        &self.params
+    }
+
+    /// Let the offspring and the archive compete for the next archive, and record it.
+    fn select(&mut self) {
+        let archive_size = self.params.archive_size.unwrap_or(self.engine.config.population_size());
+
+        let mut union = std::mem::take(&mut self.offspring);
+        union.append(&mut self.archive);
+        (self.archive, self.fitness) = environmental_selection(union, archive_size);
+
+        let fronts = non_dominated_sort(&self.archive);
+        self.engine.record(&self.archive, &fronts);
+    }
+
+    /// Parents drawn from the archive by tournaments on fitness
+    fn tournament_selection(&mut self, count: usize) -> Vec<S> {
+        let mut mating_pool = Vec::with_capacity(count);
+
+        while mating_pool.len() < count {
+            let mut winner = self.engine.rng.gen_range(0..self.archive.len());
+
+            for _ in 1..self.engine.config.tournament_size() {
+                let challenger = self.engine.rng.gen_range(0..self.archive.len());
+
+                if self.fitness[challenger] < self.fitness[winner] {
+                    winner = challenger;
+                }
+            }
+
+            mating_pool.push(self.archive[winner].solution.clone());
+        }
+
+        mating_pool
+    }
+}
+
+impl<S: Solution> WithEngine<S> for Spea2<S> {
+    fn from_engine(engine: Engine<S>) -> Self {
+        Self {
+            engine,
+            params: Spea2Params::default(),
+            offspring: Vec::new(),
+            archive: Vec::new(),
+            fitness: Vec::new(),
+        }
+    }
+
+    fn engine_mut(&mut self) -> &mut Engine<S> {
+        &mut self.engine
+    }
+}
+
+/// Generations are recorded with the archive as their population, since only
+/// its members breed and survive.
+impl<S: Solution> Algorithm<S> for Spea2<S> {
+    fn initialize(&mut self) -> Result<(), RunError> {
+        self.engine.start();
+        self.offspring = self.engine.initial_population(self.engine.config.population_size())?;
+        self.archive = Vec::new();
+        self.select();
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), RunError> {
+        let mating_pool = self.tournament_selection(self.engine.config.population_size());
+        self.offspring = self.engine.offspring(&mating_pool, mating_pool.len(), 1)?;
+
+        self.engine.generation += 1;
+        self.select();
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.engine.finished()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn Observer<S>>) {
+        self.engine.add_observer(observer);
+    }
+
+    fn population(&self) -> &[Individual<S>] {
+        &self.archive
+    }
+
+    fn archive(&self) -> Option<&Archive<S>> {
+        self.engine.archive.as_ref()
+    }
+
+    fn best(&self) -> Option<&Individual<S>> {
+        self.engine.best.as_ref()
+    }
+
+    fn config(&self) -> &Config {
+        &self.engine.config
+    }
+
+    fn statistics(&self) -> &RunStatistics {
+        &self.engine.statistics
+    }
+
+    fn history(&self) -> &RunHistory {
+        &self.engine.history
+    }
+}
+
+/// The `size` fittest members of `union` with their fitness: all non-dominated
+/// ones if they fit, topped up with the best dominated ones or truncated by
+/// crowding otherwise.
+fn environmental_selection<S>(union: Vec<Individual<S>>, size: usize) -> (Vec<Individual<S>>, Vec<f64>) {
+    let distances = distance_matrix(&union);
+    let fitness = strength_fitness(&union, &distances);
+
+    let mut selected = (0..union.len()).filter(|&i| fitness[i] < 1.0).collect::<Vec<_>>();
+
+    if selected.len() < size {
+        let mut dominated = (0..union.len()).filter(|&i| fitness[i] >= 1.0).collect::<Vec<_>>();
+        dominated.sort_by(|&a, &b| fitness[a].total_cmp(&fitness[b]));
+        selected.extend(dominated.into_iter().take(size - selected.len()));
+    } else {
+        truncate(&mut selected, &distances, size);
+    }
+
+    let selected_fitness = selected.iter().map(|&i| fitness[i]).collect();
+
+    let mut union = union.into_iter().map(Some).collect::<Vec<_>>();
+    let archive = selected.into_iter().filter_map(|i| union[i].take()).collect();
+
+    (archive, selected_fitness)
+}
+
+/// Raw fitness, the summed strength of everyone dominating an individual, plus
+/// a density below 1 from the distance to its k-th nearest neighbour.
+fn strength_fitness<S>(union: &[Individual<S>], distances: &[Vec<f64>]) -> Vec<f64> {
+    let n = union.len();
+    let k = ((n as f64).sqrt() as usize).clamp(1, n.saturating_sub(1).max(1));
+
+    let strength = (0..n)
+        .map(|i| (0..n).filter(|&j| constrained_dominates(&union[i], &union[j])).count())
+        .collect::<Vec<_>>();
+
+    (0..n)
+        .map(|i| {
+            let raw = (0..n)
+                .filter(|&j| constrained_dominates(&union[j], &union[i]))
+                .map(|j| strength[j])
+                .sum::<usize>();
+
+            let mut neighbours = (0..n).filter(|&j| j != i).map(|j| distances[i][j]).collect::<Vec<_>>();
+            neighbours.sort_by(f64::total_cmp);
+            let kth = neighbours.get(k - 1).copied().unwrap_or(f64::INFINITY);
+
+            raw as f64 + 1.0 / (kth + 2.0)
+        })
+        .collect()
+}
+
+/// Drop members of `selected` one by one until `size` are left, each time the
+/// one closest to its nearest neighbour, looking further out to break ties.
+fn truncate(selected: &mut Vec<usize>, distances: &[Vec<f64>], size: usize) {
+    while selected.len() > size {
+        let neighbours = selected
+            .iter()
+            .map(|&i| {
+                let mut row = selected.iter().filter(|&&j| j != i).map(|&j| distances[i][j]).collect::<Vec<_>>();
+                row.sort_by(f64::total_cmp);
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let crowded = (0..selected.len())
+            .min_by(|&a, &b| {
+                neighbours[a]
+                    .iter()
+                    .zip(&neighbours[b])
+                    .map(|(x, y)| x.total_cmp(y))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(0);
+
+        selected.remove(crowded);
+    }
+}
+
+/// Distances in objective space between all members of `union`. Penalized
+/// individuals are infinitely far from everyone.
+fn distance_matrix<S>(union: &[Individual<S>]) -> Vec<Vec<f64>> {
+    union
+        .iter()
+        .map(|a| {
+            union
+                .iter()
+                .map(|b| {
+                    let distance = euclidean(&a.objectives, &b.objectives);
+                    if distance.is_nan() {
+                        f64::INFINITY
+                    } else {
+                        distance
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Dominance in which feasible individuals beat infeasible ones outright.
+fn constrained_dominates<S>(a: &Individual<S>, b: &Individual<S>) -> bool {
+    if a.feasible != b.feasible {
+        return a.feasible;
+    }
+
+    a.dominates(b)
+}
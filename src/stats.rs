@@ -0,0 +1,114 @@
+//! Descriptive statistics and hypothesis tests for comparing samples of runs.
+
+pub fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// The `q`-quantile of `values`, interpolating linearly between order statistics.
+pub fn quantile(values: &[f64], q: f64) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+
+    if lower == upper {
+        sorted[lower]
+    } else {
+        sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+    }
+}
+
+pub fn median(values: &[f64]) -> f64 {
+    quantile(values, 0.5)
+}
+
+/// Interquartile range, the spread of the middle half of the values.
+pub fn iqr(values: &[f64]) -> f64 {
+    quantile(values, 0.75) - quantile(values, 0.25)
+}
+
+/// Two-sided p-value of the Wilcoxon rank-sum (Mann-Whitney U) test that `a`
+/// and `b` come from the same distribution.
+///
+/// Uses the normal approximation with tie and continuity corrections, which is
+/// adequate from about eight values per sample.
+pub fn rank_sum_test(a: &[f64], b: &[f64]) -> f64 {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+
+    if a.is_empty() || b.is_empty() {
+        return f64::NAN;
+    }
+
+    let mut combined = a
+        .iter()
+        .map(|&value| (value, true))
+        .chain(b.iter().map(|&value| (value, false)))
+        .collect::<Vec<_>>();
+    combined.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Tied values share the mean of the ranks they span
+    let n = combined.len();
+    let mut rank_sum_a = 0.0;
+    let mut tie_correction = 0.0;
+    let mut i = 0;
+
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && combined[j + 1].0.total_cmp(&combined[i].0).is_eq() {
+            j += 1;
+        }
+
+        let ties = (j - i + 1) as f64;
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+
+        rank_sum_a += rank * combined[i..=j].iter().filter(|(_, from_a)| *from_a).count() as f64;
+        tie_correction += ties.powi(3) - ties;
+        i = j + 1;
+    }
+
+    let n = n as f64;
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let expected = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+
+    if variance <= 0.0 {
+        return 1.0;
+    }
+
+    let z = ((u - expected).abs() - 0.5).max(0.0) / variance.sqrt();
+    (2.0 * (1.0 - normal_cdf(z))).min(1.0)
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, accurate to about 1.2e-7 everywhere
+/// (Numerical Recipes, Chebyshev approximation).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+
+    let polynomial = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+
+    let value = t * polynomial.exp();
+
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
@@ -0,0 +1,258 @@
+use std::io::{self, Cursor};
+use std::net::{SocketAddr, TcpStream};
+use std::process::Command;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::Value;
+
+use nsga2::distributed::protocol::{read_frame, write_frame, MasterMessage, WorkerMessage, MAX_FRAME_LEN};
+use nsga2::distributed::{run_worker, Master, MasterOptions, WorkerOptions};
+use nsga2::evaluation::{evaluate, Evaluation, EvaluationError};
+use nsga2::experiment::ExternalConfig;
+use nsga2::problems::dtlz::{Dtlz, DtlzInstance, DtlzParams};
+use nsga2::problems::external::ExternalSpec;
+use nsga2::problems::functions::{Function, FunctionInstance, FunctionParams};
+use nsga2::problems::knapsack::{KnapsackInstance, KnapsackParams};
+use nsga2::problems::quadratic::QuadraticProblem;
+use nsga2::problems::rastrigin::RastriginParams;
+use nsga2::problems::series::{SeriesInstance, SeriesParams, SeriesSystem, VariableSeriesInstance};
+use nsga2::problems::thirty::{Thirty, Zdt3Params};
+use nsga2::problems::tsp::{TspInstance, TspParams};
+use nsga2::{Evaluator, Portable, Problem};
+
+const PROBLEM: &str = "zdt3";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+type Outcome = Result<Evaluation, EvaluationError>;
+
+fn batch(size: usize, seed: u64) -> Vec<Thirty> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..size).map(|_| Zdt3Params::default().random(&mut rng)).collect()
+}
+
+fn bind() -> Master<Zdt3Params> {
+    Master::bind("127.0.0.1:0", Zdt3Params::default(), MasterOptions::default()).unwrap()
+}
+
+fn spawn_worker(addr: SocketAddr) -> JoinHandle<io::Result<()>> {
+    let options = WorkerOptions {
+        reconnect_delay: Duration::from_millis(10),
+        max_reconnects: Some(0),
+    };
+    thread::spawn(move || run_worker::<Zdt3Params>(addr, PROBLEM, &options))
+}
+
+/// Evaluate `batch` on another thread, failing the test instead of hanging
+/// if the master never completes it
+fn evaluate_in_background(
+    mut master: Master<Zdt3Params>,
+    batch: Vec<Thirty>,
+) -> mpsc::Receiver<(Master<Zdt3Params>, Vec<Outcome>)> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let outcomes = master.evaluate(&batch);
+        let _ = sender.send((master, outcomes));
+    });
+    receiver
+}
+
+#[test]
+fn batch_objectives_match_local_evaluation() {
+    let master = bind();
+    let workers = [spawn_worker(master.local_addr()), spawn_worker(master.local_addr())];
+    assert!(master.wait_for_workers(2, TIMEOUT));
+
+    let solutions = batch(50, 1);
+    let (master, outcomes) = evaluate_in_background(master, solutions.clone()).recv_timeout(TIMEOUT).unwrap();
+
+    assert_eq!(outcomes.len(), solutions.len());
+    for (solution, outcome) in solutions.iter().zip(outcomes) {
+        assert_eq!(outcome, evaluate(solution));
+    }
+
+    // Dropping the master tells both workers to stop
+    drop(master);
+    for worker in workers {
+        worker.join().unwrap().unwrap();
+    }
+}
+
+#[test]
+fn spawned_worker_process_evaluates_batches() {
+    let master = bind();
+    let mut worker = Command::new(env!("CARGO_BIN_EXE_nsga2-worker"))
+        .args(["--connect", &master.local_addr().to_string(), "--problem", PROBLEM])
+        .args(["--reconnect-delay", "0.05", "--max-reconnects", "20"])
+        .spawn()
+        .unwrap();
+
+    if !master.wait_for_workers(1, TIMEOUT) {
+        worker.kill().unwrap();
+        panic!("the worker process never connected");
+    }
+
+    let solutions = batch(30, 3);
+    let (master, outcomes) = evaluate_in_background(master, solutions.clone()).recv_timeout(TIMEOUT).unwrap();
+
+    for (solution, outcome) in solutions.iter().zip(outcomes) {
+        assert_eq!(outcome, evaluate(solution));
+    }
+
+    drop(master);
+    assert!(worker.wait().unwrap().success());
+}
+
+#[test]
+fn worker_refuses_parameters_of_another_problem_or_invalid_ones() {
+    let options = WorkerOptions {
+        reconnect_delay: Duration::from_millis(10),
+        max_reconnects: Some(5),
+    };
+
+    // A master that answers the hello with `params`, returning the error the worker stops with
+    let refused = |params: Value, hosted: &'static str| {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let options = options.clone();
+        let worker = thread::spawn(move || run_worker::<Zdt3Params>(addr, hosted, &options));
+
+        let (mut stream, _) = listener.accept().unwrap();
+        assert!(matches!(read_frame(&mut stream).unwrap(), WorkerMessage::Hello { .. }));
+        write_frame(&mut stream, &MasterMessage::<_, Value>::Problem { params }).unwrap();
+
+        let error = worker.join().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        error.to_string()
+    };
+
+    assert!(refused(serde_json::json!({ "variables": 1 }), PROBLEM).contains("at least 2 variables"));
+    assert!(refused(serde_json::json!({ "dimension": 20 }), PROBLEM).contains("unknown field"));
+    assert!(refused(serde_json::json!({ "variables": 30 }), "rastrigin").contains("parameters of zdt3"));
+}
+
+#[test]
+fn job_of_lost_worker_is_finished_by_another() {
+    let master = bind();
+    let addr = master.local_addr();
+
+    // A worker that takes a job and disconnects without answering
+    let mut lost = TcpStream::connect(addr).unwrap();
+    write_frame(&mut lost, &WorkerMessage::Hello { problem: PROBLEM.to_string() }).unwrap();
+    match read_frame::<_, MasterMessage<Zdt3Params, Value>>(&mut lost).unwrap() {
+        MasterMessage::Problem { params } => assert_eq!(params, Zdt3Params::default()),
+        message => panic!("expected the problem, got {:?}", message),
+    }
+    write_frame(&mut lost, &WorkerMessage::Ready).unwrap();
+    assert!(master.wait_for_workers(1, TIMEOUT));
+
+    let solutions = batch(20, 2);
+    let evaluation = evaluate_in_background(master, solutions.clone());
+
+    let taken = match read_frame::<_, MasterMessage<Value, Value>>(&mut lost).unwrap() {
+        MasterMessage::Job { genome, .. } => genome,
+        message => panic!("expected a job, got {:?}", message),
+    };
+
+    // Only now does a second worker join, while the lost one still holds its job
+    let worker = spawn_worker(addr);
+    drop(lost);
+
+    let (master, outcomes) = evaluation.recv_timeout(TIMEOUT).unwrap();
+
+    assert_eq!(outcomes.len(), solutions.len());
+    for (solution, outcome) in solutions.iter().zip(&outcomes) {
+        assert_eq!(*outcome, evaluate(solution));
+    }
+
+    let index = solutions.iter().position(|solution| serde_json::to_value(solution).unwrap() == taken).unwrap();
+    assert!(outcomes[index].is_ok());
+    assert_eq!(master.workers(), 1);
+
+    drop(master);
+    worker.join().unwrap().unwrap();
+}
+
+#[test]
+fn frames_carry_a_big_endian_length_prefix() {
+    let mut buffer = Vec::new();
+    write_frame(&mut buffer, &WorkerMessage::Hello { problem: PROBLEM.to_string() }).unwrap();
+    let outcome = Ok(Evaluation { objectives: vec![0.5, -1.25], feasible: true });
+    write_frame(&mut buffer, &WorkerMessage::Result { id: 7, outcome: outcome.clone() }).unwrap();
+
+    let length = u32::from_be_bytes(buffer[..4].try_into().unwrap()) as usize;
+    let body: Value = serde_json::from_slice(&buffer[4..4 + length]).unwrap();
+    assert_eq!(body, serde_json::json!({ "type": "hello", "problem": PROBLEM }));
+
+    let mut reader = Cursor::new(buffer);
+    match read_frame(&mut reader).unwrap() {
+        WorkerMessage::Hello { problem } => assert_eq!(problem, PROBLEM),
+        message => panic!("expected a hello, got {:?}", message),
+    }
+    match read_frame(&mut reader).unwrap() {
+        WorkerMessage::Result { id, outcome: received } => {
+            assert_eq!(id, 7);
+            assert_eq!(received, outcome);
+        }
+        message => panic!("expected a result, got {:?}", message),
+    }
+
+    let error = read_frame::<_, WorkerMessage>(&mut reader).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn frames_reject_oversized_and_truncated_messages() {
+    let oversized = ((MAX_FRAME_LEN + 1) as u32).to_be_bytes();
+    let error = read_frame::<_, WorkerMessage>(&mut Cursor::new(oversized)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let mut buffer = Vec::new();
+    write_frame(&mut buffer, &MasterMessage::<(), _>::Job { id: 3, genome: vec![1.0, 2.0] }).unwrap();
+    buffer.pop();
+    let error = read_frame::<_, MasterMessage<(), Vec<f64>>>(&mut Cursor::new(buffer)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+/// `problem` rebuilt from its parameters the way a worker receives them
+fn rebuilt<P: Portable>(problem: &P) -> P {
+    let params = serde_json::to_value(problem.params()).unwrap();
+    let instance = P::from_params(serde_json::from_value(params.clone()).unwrap()).unwrap();
+
+    assert_eq!(instance.name(), problem.name());
+    assert_eq!(serde_json::to_value(instance.params()).unwrap(), params);
+    instance
+}
+
+#[test]
+fn every_problem_is_rebuilt_from_the_parameters_it_sends() {
+    rebuilt(&QuadraticProblem);
+    rebuilt(&Zdt3Params { variables: 12 });
+    rebuilt(&RastriginParams { dimension: 7 });
+    rebuilt(&Arc::new(FunctionInstance::new(FunctionParams { function: Function::Griewank, dimension: 4 })));
+    rebuilt(&Arc::new(DtlzInstance::new(DtlzParams { objectives: 5, ..DtlzParams::preset(Dtlz::Dtlz1) })));
+    rebuilt(&Arc::new(SeriesInstance::new(SeriesParams::preset(SeriesSystem::Bridge))));
+    rebuilt(&Arc::new(VariableSeriesInstance::new(Default::default())));
+    rebuilt(&Arc::new(TspInstance::new(TspParams { cities: 12, ..TspParams::default() })));
+
+    // Instances generated from a seed come out the same on the worker
+    let knapsack = Arc::new(KnapsackInstance::new(KnapsackParams { instance_seed: 9, ..KnapsackParams::default() }));
+    assert_eq!(rebuilt(&knapsack).profits, knapsack.profits);
+
+    let external = ExternalConfig {
+        program: "evaluate".into(),
+        bounds: vec![(0.0, 1.0), (-2.0, 2.0)],
+        objectives: 2,
+        timeout_seconds: Some(1.5),
+        ..ExternalConfig::default()
+    };
+    let spec = rebuilt(&Arc::new(external.spec()));
+    assert_eq!(spec.timeout, Duration::from_secs_f64(1.5));
+
+    let invalid = ExternalConfig { bounds: vec![(1.0, 0.0)], ..external };
+    assert!(<Arc<ExternalSpec>>::from_params(invalid).is_err());
+}